@@ -0,0 +1,143 @@
+use thiserror::Error;
+
+/// Timezones whose country is unambiguous, used when the locale names none
+const TIMEZONE_COUNTRIES: &[(&str, &str)] = &[
+    ("America/New_York", "US"),
+    ("America/Chicago", "US"),
+    ("America/Denver", "US"),
+    ("America/Phoenix", "US"),
+    ("America/Los_Angeles", "US"),
+    ("America/Anchorage", "US"),
+    ("Pacific/Honolulu", "US"),
+    ("America/Toronto", "CA"),
+    ("America/Vancouver", "CA"),
+    ("America/Mexico_City", "MX"),
+    ("America/Sao_Paulo", "BR"),
+    ("America/Argentina/Buenos_Aires", "AR"),
+    ("Europe/London", "GB"),
+    ("Europe/Dublin", "IE"),
+    ("Europe/Paris", "FR"),
+    ("Europe/Berlin", "DE"),
+    ("Europe/Madrid", "ES"),
+    ("Europe/Rome", "IT"),
+    ("Europe/Amsterdam", "NL"),
+    ("Europe/Warsaw", "PL"),
+    ("Europe/Kiev", "UA"),
+    ("Europe/Kyiv", "UA"),
+    ("Europe/Istanbul", "TR"),
+    ("Europe/Moscow", "RU"),
+    ("Africa/Lagos", "NG"),
+    ("Africa/Cairo", "EG"),
+    ("Africa/Johannesburg", "ZA"),
+    ("Asia/Dubai", "AE"),
+    ("Asia/Kolkata", "IN"),
+    ("Asia/Calcutta", "IN"),
+    ("Asia/Singapore", "SG"),
+    ("Asia/Ho_Chi_Minh", "VN"),
+    ("Asia/Jakarta", "ID"),
+    ("Asia/Manila", "PH"),
+    ("Asia/Shanghai", "CN"),
+    ("Asia/Hong_Kong", "HK"),
+    ("Asia/Seoul", "KR"),
+    ("Asia/Tokyo", "JP"),
+    ("Australia/Sydney", "AU"),
+    ("Pacific/Auckland", "NZ"),
+];
+
+#[derive(Debug, Error)]
+#[error("invalid location {0:?}: expected an ISO 3166 country code such as \"DE\" or a region code such as \"US-CA\"")]
+pub struct InvalidLocation(pub String);
+
+/// Validates an ISO 3166-1 alpha-2 country code, optionally followed by an
+/// ISO 3166-2 subdivision (`CC-XXX`), and returns it in canonical upper case.
+pub fn validate_location(location: &str) -> Result<String, InvalidLocation> {
+    let normalized = location.trim().to_ascii_uppercase();
+    let (country, region) = match normalized.split_once('-') {
+        Some((country, region)) => (country, Some(region)),
+        None => (normalized.as_str(), None),
+    };
+
+    let country_valid = country.len() == 2 && country.bytes().all(|b| b.is_ascii_uppercase());
+    let region_valid = region.map_or(true, |region| {
+        (1..=3).contains(&region.len()) && region.bytes().all(|b| b.is_ascii_alphanumeric())
+    });
+    if country_valid && region_valid {
+        Ok(normalized)
+    } else {
+        Err(InvalidLocation(location.to_string()))
+    }
+}
+
+/// Guesses the country from the system locale (e.g. `en_GB.UTF-8`), falling
+/// back to the system timezone. Works offline; returns `None` if unsure.
+pub fn detect_location() -> Option<String> {
+    country_from_locale().or_else(country_from_timezone)
+}
+
+fn country_from_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|locale| {
+            // language[_territory][.codeset][@modifier]
+            let locale = locale.split(['.', '@']).next()?;
+            let (_, territory) = locale.split_once('_')?;
+            validate_location(territory).ok()
+        })
+}
+
+fn country_from_timezone() -> Option<String> {
+    let timezone = std::env::var("TZ")
+        .ok()
+        .map(|tz| tz.trim_start_matches(':').to_string())
+        .or_else(|| {
+            std::fs::read_to_string("/etc/timezone")
+                .ok()
+                .map(|tz| tz.trim().to_string())
+        })
+        .or_else(|| {
+            // /etc/localtime -> /usr/share/zoneinfo/Region/City
+            let target = std::fs::read_link("/etc/localtime").ok()?;
+            let target = target.to_str()?;
+            let (_, zone) = target.split_once("zoneinfo/")?;
+            Some(zone.to_string())
+        })?;
+
+    TIMEZONE_COUNTRIES
+        .iter()
+        .find(|(zone, _)| *zone == timezone)
+        .map(|(_, country)| country.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_country_and_region_codes() {
+        assert_eq!(validate_location("DE").unwrap(), "DE");
+        assert_eq!(validate_location("US-CA").unwrap(), "US-CA");
+        assert_eq!(validate_location("GB-ENG").unwrap(), "GB-ENG");
+        assert_eq!(validate_location("FR-75").unwrap(), "FR-75");
+    }
+
+    #[test]
+    fn normalizes_case_and_whitespace() {
+        assert_eq!(validate_location(" de ").unwrap(), "DE");
+        assert_eq!(validate_location("us-ca").unwrap(), "US-CA");
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        for location in [
+            "", "D", "DEU", "D1", "Germany", "US-", "US-CALI", "US-C!", "-CA", "US_CA",
+        ] {
+            assert!(
+                validate_location(location).is_err(),
+                "{:?} should be rejected",
+                location
+            );
+        }
+    }
+}
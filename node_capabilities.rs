@@ -0,0 +1,116 @@
+use crate::flops::measure_flops;
+use crate::memory_stats::get_memory_info;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Capabilities of this machine, expensive to measure, so measured once and
+/// shared between telemetry reports until they are refreshed
+pub type SharedCapabilities = Arc<RwLock<NodeCapabilities>>;
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Benchmarked capabilities of this node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeCapabilities {
+    pub flops_per_sec: f64,
+    /// Total system memory, in the units reported by `get_memory_info`
+    pub memory_capacity: i32,
+    /// Seconds since the Unix epoch at which the measurement was taken
+    pub measured_at: u64,
+}
+
+impl NodeCapabilities {
+    /// Runs the benchmarks. This keeps the CPU busy for a while and should not
+    /// be called on the async runtime.
+    pub fn measure() -> Self {
+        let (_, memory_capacity) = get_memory_info();
+        Self {
+            flops_per_sec: measure_flops() as f64,
+            memory_capacity,
+            measured_at: now_secs(),
+        }
+    }
+
+    /// The cache file location, `~/.nexus/capabilities.json`.
+    pub fn path() -> Option<PathBuf> {
+        home::home_dir().map(|home| home.join(".nexus").join("capabilities.json"))
+    }
+
+    /// Time since the measurement was taken
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now_secs().saturating_sub(self.measured_at))
+    }
+
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() >= max_age
+    }
+
+    /// Loads the cached measurement if it is younger than `max_age`, otherwise
+    /// measures again and updates the cache.
+    pub fn load_or_measure(max_age: Duration) -> Self {
+        if let Some(cached) = Self::load().filter(|cached| !cached.is_stale(max_age)) {
+            return cached;
+        }
+        let measured = Self::measure();
+        if let Err(e) = measured.save() {
+            println!("Failed to cache node capabilities: {}", e);
+        }
+        measured
+    }
+
+    fn load() -> Option<Self> {
+        let contents = std::fs::read_to_string(Self::path()?).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "home directory not found"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, contents)
+    }
+
+    /// FLOPS as reported in telemetry. Float-to-int casts saturate, so
+    /// out-of-range or non-finite measurements never wrap around.
+    pub fn flops_per_sec_reported(&self) -> i64 {
+        self.flops_per_sec.round() as i64
+    }
+}
+
+/// Re-measures capabilities on the blocking thread pool once the current
+/// measurement is `interval` old, and every `interval` after that.
+pub fn spawn_refresher(
+    capabilities: SharedCapabilities,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    // A cached measurement may already be most of `interval` old
+    let first_refresh = interval.saturating_sub(capabilities.read().unwrap().age());
+    tokio::spawn(async move {
+        let mut ticker =
+            tokio::time::interval_at(tokio::time::Instant::now() + first_refresh, interval);
+        loop {
+            ticker.tick().await;
+            match tokio::task::spawn_blocking(NodeCapabilities::measure).await {
+                Ok(measured) => {
+                    if let Err(e) = measured.save() {
+                        println!("Failed to cache node capabilities: {}", e);
+                    }
+                    *capabilities.write().unwrap() = measured;
+                }
+                Err(e) => println!("Failed to measure node capabilities: {}", e),
+            }
+        }
+    })
+}
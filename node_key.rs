@@ -0,0 +1,179 @@
+use ed25519_dalek::{Signature, Signer, SigningKey};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NodeKeyError {
+    #[error("home directory not found")]
+    NoHomeDir,
+
+    #[error("failed to access node key {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("node key {path} is not a hex-encoded 32-byte Ed25519 secret key")]
+    Invalid { path: PathBuf },
+}
+
+/// Ed25519 key identifying this machine, stored hex-encoded in
+/// `~/.nexus/node_key` and readable only by its owner
+pub struct NodeKey {
+    signing_key: SigningKey,
+}
+
+impl NodeKey {
+    /// The key file location, `~/.nexus/node_key`.
+    pub fn path() -> Option<PathBuf> {
+        home::home_dir().map(|home| home.join(".nexus").join("node_key"))
+    }
+
+    pub fn generate() -> Self {
+        Self {
+            signing_key: SigningKey::generate(&mut rand::rngs::OsRng),
+        }
+    }
+
+    /// Loads the key at `path`, or returns `None` if there is none.
+    pub fn load(path: &Path) -> Result<Option<Self>, NodeKeyError> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(NodeKeyError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let secret: [u8; 32] = hex::decode(contents.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| NodeKeyError::Invalid {
+                path: path.to_path_buf(),
+            })?;
+        Ok(Some(Self {
+            signing_key: SigningKey::from_bytes(&secret),
+        }))
+    }
+
+    /// Loads the node key, generating and saving one if there is none yet.
+    pub fn load_or_generate() -> Result<Self, NodeKeyError> {
+        let path = Self::path().ok_or(NodeKeyError::NoHomeDir)?;
+        if let Some(key) = Self::load(&path)? {
+            return Ok(key);
+        }
+        let key = Self::generate();
+        key.save(&path)?;
+        Ok(key)
+    }
+
+    /// Writes the key to `path`, replacing any existing key atomically.
+    pub fn save(&self, path: &Path) -> Result<(), NodeKeyError> {
+        let io_error = |source| NodeKeyError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+
+        let tmp = path.with_extension("tmp");
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&tmp).map_err(io_error)?;
+        file.write_all(hex::encode(self.signing_key.to_bytes()).as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(io_error)?;
+        std::fs::rename(&tmp, path).map_err(io_error)
+    }
+
+    pub fn public_key_bytes(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    /// The hex-encoded public key, as registered with the orchestrator.
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.public_key_bytes())
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.signing_key.sign(message)
+    }
+
+    /// Signs a statement that this node produced the proof with `proof_hash`
+    /// for `task_id`. The signed message is
+    /// `nexus-proof-attestation\n<task_id>\n<proof_hash>\n<node_id>`.
+    pub fn attest(&self, task_id: &str, proof_hash: &str, node_id: &str) -> Signature {
+        self.sign(attestation_message(task_id, proof_hash, node_id).as_bytes())
+    }
+}
+
+fn attestation_message(task_id: &str, proof_hash: &str, node_id: &str) -> String {
+    format!(
+        "nexus-proof-attestation\n{}\n{}\n{}",
+        task_id, proof_hash, node_id
+    )
+}
+
+/// Manage the key identifying this node
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum KeysCommand {
+    /// Print the node's public key, generating a key pair if there is none
+    Show,
+    /// Write the node's public key to a file, or to stdout
+    Export {
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Replace the node key with a new one, keeping the old key as
+    /// `node_key.old`. Register the new public key before submitting again.
+    Rotate,
+}
+
+pub fn run_keys_command(command: &KeysCommand) -> Result<(), NodeKeyError> {
+    let path = NodeKey::path().ok_or(NodeKeyError::NoHomeDir)?;
+    match command {
+        KeysCommand::Show => {
+            let key = NodeKey::load_or_generate()?;
+            println!("Public key: {}", key.public_key_hex());
+            println!("Key file: {}", path.display());
+        }
+        KeysCommand::Export { output } => {
+            let key = NodeKey::load_or_generate()?;
+            match output {
+                Some(output) => {
+                    std::fs::write(output, format!("{}\n", key.public_key_hex())).map_err(
+                        |source| NodeKeyError::Io {
+                            path: output.clone(),
+                            source,
+                        },
+                    )?;
+                    println!("Public key written to {}", output.display());
+                }
+                None => println!("{}", key.public_key_hex()),
+            }
+        }
+        KeysCommand::Rotate => {
+            let old = NodeKey::load(&path)?;
+            if let Some(old) = &old {
+                old.save(&path.with_extension("old"))?;
+            }
+            let key = NodeKey::generate();
+            key.save(&path)?;
+            if let Some(old) = old {
+                println!("Old public key: {}", old.public_key_hex());
+            }
+            println!("New public key: {}", key.public_key_hex());
+        }
+    }
+    Ok(())
+}
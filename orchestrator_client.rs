@@ -1,75 +1,743 @@
 use crate::config;
-use crate::flops::measure_flops;
-use crate::memory_stats::get_memory_info;
 use crate::nexus_orchestrator::{
-    GetProofTaskRequest, GetProofTaskResponse, NodeType, SubmitProofRequest,
+    FinalizeProofUploadRequest, GetProofTaskRequest, GetProofTaskResponse,
+    InitiateProofUploadRequest, InitiateProofUploadResponse, NodeType, ProofEncoding,
+    ReportTaskFailureRequest, SubmitProofRequest, TaskFailureCategory, UploadProofChunkRequest,
+    UploadProofChunkResponse,
 };
+use crate::node_capabilities::SharedCapabilities;
+use crate::node_key::NodeKey;
+use crate::proof_codec::ProofCodec;
+use crate::telemetry::{self, ProofMetrics};
 use prost::Message;
-use reqwest::Client;
+use rand::Rng;
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Certificate, Client, Proxy, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Errors returned by the orchestrator client, classified so that callers can
+/// decide whether to retry, back off or give up.
+#[derive(Debug, Error)]
+pub enum OrchestratorError {
+    /// The request never produced an HTTP response (DNS, connect, TLS, reset...).
+    #[error("failed to reach orchestrator at {url}: {source}")]
+    Transport {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// The orchestrator asked us to slow down (HTTP 429).
+    #[error("orchestrator rate limited request to {url} (HTTP {status}): {body}")]
+    RateLimited {
+        url: String,
+        status: StatusCode,
+        body: String,
+        retry_after: Option<Duration>,
+    },
+
+    /// The request was rejected by the orchestrator (HTTP 4xx other than 429).
+    #[error("orchestrator rejected request to {url} (HTTP {status}): {body}")]
+    Client {
+        url: String,
+        status: StatusCode,
+        body: String,
+    },
+
+    /// The orchestrator failed to handle the request (HTTP 5xx).
+    #[error("orchestrator failed to handle request to {url} (HTTP {status}): {body}")]
+    Server {
+        url: String,
+        status: StatusCode,
+        body: String,
+    },
+
+    /// Any other non-success status (1xx/3xx).
+    #[error("unexpected response from orchestrator at {url} (HTTP {status}): {body}")]
+    UnexpectedStatus {
+        url: String,
+        status: StatusCode,
+        body: String,
+    },
+
+    /// The response body was not a valid protobuf message.
+    #[error("failed to decode response from {url}: {source}")]
+    Decode {
+        url: String,
+        #[source]
+        source: prost::DecodeError,
+    },
+
+    /// The orchestrator returned no body where one was required.
+    #[error("empty response from orchestrator at {url}")]
+    EmptyResponse { url: String },
+
+    /// The request was rejected locally before being sent.
+    #[error("invalid request: {0}")]
+    Validation(String),
+
+    /// The orchestrator sent a task that cannot be proven or submitted.
+    #[error("invalid task from {url}: {reason}")]
+    InvalidTask { url: String, reason: String },
+
+    /// The orchestrator accepted a proof chunk without storing any of it.
+    #[error("proof upload to {url} stalled at offset {offset}")]
+    UploadStalled { url: String, offset: u64 },
+
+    /// The HTTP client could not be built from its [`ClientConfig`].
+    #[error("invalid HTTP client configuration: {0}")]
+    Config(String),
+}
+
+impl OrchestratorError {
+    /// Classifies a non-success HTTP response.
+    fn from_status(
+        url: String,
+        status: StatusCode,
+        body: String,
+        retry_after: Option<Duration>,
+    ) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            Self::RateLimited {
+                url,
+                status,
+                body,
+                retry_after,
+            }
+        } else if status.is_client_error() {
+            Self::Client { url, status, body }
+        } else if status.is_server_error() {
+            Self::Server { url, status, body }
+        } else {
+            Self::UnexpectedStatus { url, status, body }
+        }
+    }
+
+    /// The HTTP status code returned by the orchestrator, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::RateLimited { status, .. }
+            | Self::Client { status, .. }
+            | Self::Server { status, .. }
+            | Self::UnexpectedStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Transport { .. } | Self::RateLimited { .. } | Self::Server { .. } => true,
+            Self::EmptyResponse { .. } | Self::UploadStalled { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the request may be sent again by the client's retry loop.
+    ///
+    /// Non-idempotent requests (proof submission) are only retried when the
+    /// orchestrator cannot have processed them: the connection was never
+    /// established, or the server explicitly refused the request with 429/503.
+    /// Timeouts and gateway errors may hide an accepted submission.
+    fn is_retryable(&self, idempotent: bool) -> bool {
+        match self {
+            Self::Transport { source, .. } => {
+                source.is_connect() || (idempotent && source.is_timeout())
+            }
+            Self::RateLimited { .. } => true,
+            Self::Server { status, .. } => match *status {
+                StatusCode::SERVICE_UNAVAILABLE => true,
+                StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT => idempotent,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether the orchestrator, or local validation, refused this particular
+    /// request, so that sending it again can never succeed. Authorization and
+    /// configuration errors are not rejections: they can be fixed, after
+    /// which the same request succeeds.
+    pub fn is_rejection(&self) -> bool {
+        match self {
+            Self::Client { .. } => !self.is_fatal(),
+            Self::Validation(_) | Self::InvalidTask { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether no further request from this node can succeed, e.g. because
+    /// the node is not authorized.
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            Self::Client { status, .. } => {
+                *status == StatusCode::UNAUTHORIZED || *status == StatusCode::FORBIDDEN
+            }
+            Self::Validation(_) | Self::Config(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Controls how transient request failures are retried.
+///
+/// Delays follow exponential backoff with full jitter: before retry `n` the
+/// client sleeps a random duration in `[0, min(max_delay, base_delay * 2^n)]`,
+/// unless the orchestrator sent a `Retry-After` header. A `Retry-After` longer
+/// than `max_delay` is not waited out; the error is returned instead.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1u32 << retry.min(16))
+            .min(self.max_delay);
+        rand::thread_rng().gen_range(Duration::ZERO..=ceiling)
+    }
+
+    /// The delay before retry `retry`, or `None` if the orchestrator asked
+    /// for a longer wait than `max_delay`, in which case the error is
+    /// returned to the caller instead of sleeping inside the request.
+    fn delay_for(&self, retry: u32, error: &OrchestratorError) -> Option<Duration> {
+        match error {
+            OrchestratorError::RateLimited {
+                retry_after: Some(retry_after),
+                ..
+            } => (*retry_after <= self.max_delay).then_some(*retry_after),
+            _ => Some(self.backoff(retry)),
+        }
+    }
+}
+
+/// HTTP methods used by orchestrator endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    /// Whether sending the request twice has the same effect as sending it
+    /// once, which makes it safe to retry after an ambiguous failure.
+    pub fn is_idempotent(self) -> bool {
+        !matches!(self, Self::Post)
+    }
+
+    fn allows_body(self) -> bool {
+        !matches!(self, Self::Get)
+    }
+}
+
+impl From<Method> for reqwest::Method {
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Get => reqwest::Method::GET,
+            Method::Post => reqwest::Method::POST,
+            Method::Put => reqwest::Method::PUT,
+            Method::Delete => reqwest::Method::DELETE,
+        }
+    }
+}
+
+/// A request to an orchestrator endpoint whose successful response decodes
+/// to `U`. Use `()` for endpoints that return no body.
+#[derive(Debug, Clone)]
+pub struct ApiRequest<U> {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+    /// Accepted statuses; any 2xx when empty
+    expected_statuses: Vec<StatusCode>,
+    idempotent: bool,
+    response: PhantomData<fn() -> U>,
+}
+
+impl<U: Message + Default> ApiRequest<U> {
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            query: Vec::new(),
+            body: None,
+            expected_statuses: Vec::new(),
+            idempotent: method.is_idempotent(),
+            response: PhantomData,
+        }
+    }
+
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::Get, path)
+    }
+
+    pub fn post(path: impl Into<String>) -> Self {
+        Self::new(Method::Post, path)
+    }
+
+    pub fn put(path: impl Into<String>) -> Self {
+        Self::new(Method::Put, path)
+    }
+
+    pub fn delete(path: impl Into<String>) -> Self {
+        Self::new(Method::Delete, path)
+    }
+
+    /// Appends a query parameter.
+    pub fn query(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.query.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Sets the protobuf-encoded request body. Rejected for GET requests.
+    pub fn body<T: Message>(mut self, body: &T) -> Self {
+        self.body = Some(body.encode_to_vec());
+        self
+    }
+
+    /// Accepts `status` as success. Once set, only the listed statuses are
+    /// accepted rather than any 2xx.
+    pub fn expect_status(mut self, status: StatusCode) -> Self {
+        self.expected_statuses.push(status);
+        self
+    }
+
+    /// Overrides whether the request may be retried after an ambiguous
+    /// failure, for POST endpoints that are idempotent by design.
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    fn accepts(&self, status: StatusCode) -> bool {
+        if self.expected_statuses.is_empty() {
+            status.is_success()
+        } else {
+            self.expected_statuses.contains(&status)
+        }
+    }
+}
+
+/// Settings for the underlying HTTP client, read from the `http` section of
+/// the settings file. Besides `proxy`, the standard `HTTPS_PROXY`,
+/// `HTTP_PROXY` and `ALL_PROXY` variables are honoured.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientConfig {
+    /// Time allowed to establish a connection (`NEXUS_CONNECT_TIMEOUT_SECS`)
+    pub connect_timeout_secs: u64,
+
+    /// Time allowed for a whole request, including uploading the proof
+    /// (`NEXUS_REQUEST_TIMEOUT_SECS`)
+    pub request_timeout_secs: u64,
+
+    /// Proxy for all orchestrator requests, e.g. `http://proxy:3128` or
+    /// `socks5://127.0.0.1:1080` (`NEXUS_PROXY`)
+    pub proxy: Option<String>,
+
+    /// PEM file of extra root certificates to trust, for networks that
+    /// intercept TLS (`NEXUS_CA_BUNDLE`)
+    pub ca_bundle: Option<PathBuf>,
+
+    /// How long idle connections are kept open (`NEXUS_POOL_IDLE_TIMEOUT_SECS`)
+    pub pool_idle_timeout_secs: u64,
+
+    /// Maximum idle connections kept per host (`NEXUS_POOL_MAX_IDLE_PER_HOST`)
+    pub pool_max_idle_per_host: usize,
+
+    /// Proofs larger than this many bytes, after compression, are uploaded in
+    /// chunks that can be resumed (`NEXUS_CHUNKED_UPLOAD_THRESHOLD`)
+    pub chunked_upload_threshold: usize,
+
+    /// Size of each chunk of a chunked upload, in bytes (`NEXUS_UPLOAD_CHUNK_SIZE`)
+    pub upload_chunk_size: usize,
+
+    /// Attempts per request, including the first one; 1 disables retries
+    /// (`NEXUS_RETRY_MAX_ATTEMPTS`)
+    pub retry_max_attempts: u32,
+
+    /// Base delay of the exponential backoff between retries
+    /// (`NEXUS_RETRY_BASE_DELAY_MS`)
+    pub retry_base_delay_ms: u64,
+
+    /// Longest wait between retries, including one asked for with
+    /// `Retry-After` (`NEXUS_RETRY_MAX_DELAY_SECS`)
+    pub retry_max_delay_secs: u64,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: 10,
+            request_timeout_secs: 120,
+            proxy: None,
+            ca_bundle: None,
+            pool_idle_timeout_secs: 90,
+            pool_max_idle_per_host: 4,
+            chunked_upload_threshold: 8 * 1024 * 1024,
+            upload_chunk_size: 1024 * 1024,
+            retry_max_attempts: 5,
+            retry_base_delay_ms: 500,
+            retry_max_delay_secs: 30,
+        }
+    }
+}
+
+impl ClientConfig {
+    pub(crate) fn apply_env(&mut self) {
+        fn parsed<T: std::str::FromStr>(var: &str) -> Option<T> {
+            std::env::var(var).ok().and_then(|value| value.parse().ok())
+        }
+
+        if let Some(secs) = parsed("NEXUS_CONNECT_TIMEOUT_SECS") {
+            self.connect_timeout_secs = secs;
+        }
+        if let Some(secs) = parsed("NEXUS_REQUEST_TIMEOUT_SECS") {
+            self.request_timeout_secs = secs;
+        }
+        if let Ok(proxy) = std::env::var("NEXUS_PROXY") {
+            self.proxy = Some(proxy);
+        }
+        if let Ok(path) = std::env::var("NEXUS_CA_BUNDLE") {
+            self.ca_bundle = Some(PathBuf::from(path));
+        }
+        if let Some(secs) = parsed("NEXUS_POOL_IDLE_TIMEOUT_SECS") {
+            self.pool_idle_timeout_secs = secs;
+        }
+        if let Some(max) = parsed("NEXUS_POOL_MAX_IDLE_PER_HOST") {
+            self.pool_max_idle_per_host = max;
+        }
+        if let Some(threshold) = parsed("NEXUS_CHUNKED_UPLOAD_THRESHOLD") {
+            self.chunked_upload_threshold = threshold;
+        }
+        if let Some(size) = parsed("NEXUS_UPLOAD_CHUNK_SIZE") {
+            self.upload_chunk_size = size;
+        }
+        if let Some(attempts) = parsed("NEXUS_RETRY_MAX_ATTEMPTS") {
+            self.retry_max_attempts = attempts;
+        }
+        if let Some(ms) = parsed("NEXUS_RETRY_BASE_DELAY_MS") {
+            self.retry_base_delay_ms = ms;
+        }
+        if let Some(secs) = parsed("NEXUS_RETRY_MAX_DELAY_SECS") {
+            self.retry_max_delay_secs = secs;
+        }
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            // Every request is sent at least once
+            max_attempts: self.retry_max_attempts.max(1),
+            base_delay: Duration::from_millis(self.retry_base_delay_ms),
+            max_delay: Duration::from_secs(self.retry_max_delay_secs),
+        }
+    }
+
+    fn build_client(&self, node_id: Option<&str>) -> Result<Client, OrchestratorError> {
+        let user_agent = match node_id {
+            Some(node_id) => format!("nexus-cli/{} (node {})", telemetry::CLIENT_VERSION, node_id),
+            None => format!("nexus-cli/{}", telemetry::CLIENT_VERSION),
+        };
+        let mut builder = Client::builder()
+            .user_agent(user_agent)
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .timeout(Duration::from_secs(self.request_timeout_secs))
+            .pool_idle_timeout(Duration::from_secs(self.pool_idle_timeout_secs))
+            .pool_max_idle_per_host(self.pool_max_idle_per_host);
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy.as_str()).map_err(|e| {
+                OrchestratorError::Config(format!("invalid proxy {}: {}", proxy, e))
+            })?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(path) = &self.ca_bundle {
+            let pem = std::fs::read(path).map_err(|e| {
+                OrchestratorError::Config(format!("failed to read {}: {}", path.display(), e))
+            })?;
+            let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| {
+                OrchestratorError::Config(format!("invalid CA bundle {}: {}", path.display(), e))
+            })?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        builder
+            .build()
+            .map_err(|e| OrchestratorError::Config(e.to_string()))
+    }
+}
+
+/// Times a chunked upload is restarted from the orchestrator's last committed
+/// offset after its chunk requests ran out of retries
+const MAX_UPLOAD_RESUMES: u32 = 3;
+
+/// Headers carrying a request signature, see [`OrchestratorClient::with_request_signing`]
+const PUBLIC_KEY_HEADER: &str = "X-Nexus-Public-Key";
+const TIMESTAMP_HEADER: &str = "X-Nexus-Timestamp";
+const NONCE_HEADER: &str = "X-Nexus-Nonce";
+const SIGNATURE_HEADER: &str = "X-Nexus-Signature";
 
 pub struct OrchestratorClient {
     client: Client,
     base_url: String,
+    retry_policy: RetryPolicy,
+    /// Validated ISO 3166 code reported in telemetry
+    location: Option<String>,
+    capabilities: Option<SharedCapabilities>,
+    /// Sent as a bearer token with every request
+    api_key: Option<String>,
+    /// Attests submitted proofs, and signs requests if `sign_requests` is set
+    node_key: Option<Arc<NodeKey>>,
+    sign_requests: bool,
+    proof_codec: ProofCodec,
+    /// Set once the orchestrator rejects `proof_codec`, after which proofs
+    /// are sent uncompressed
+    compression_rejected: AtomicBool,
+    chunked_upload_threshold: usize,
+    upload_chunk_size: usize,
 }
 
 impl OrchestratorClient {
-    pub fn new(environment: config::Environment) -> Self {
-        Self {
-            client: Client::new(),
+    /// Creates a client from `config`, identifying the node in the
+    /// `User-Agent` header when `node_id` is given.
+    pub fn new_with_config(
+        environment: config::Environment,
+        config: &ClientConfig,
+        node_id: Option<&str>,
+    ) -> Result<Self, OrchestratorError> {
+        Ok(Self {
+            client: config.build_client(node_id)?,
             base_url: environment.orchestrator_url(),
+            retry_policy: config.retry_policy(),
+            location: None,
+            capabilities: None,
+            api_key: None,
+            node_key: None,
+            sign_requests: false,
+            proof_codec: ProofCodec::None,
+            compression_rejected: AtomicBool::new(false),
+            chunked_upload_threshold: config.chunked_upload_threshold,
+            // An empty chunk would never advance the upload
+            upload_chunk_size: config.upload_chunk_size.max(1),
+        })
+    }
+
+    /// Authenticates every request with `api_key` as a bearer token.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key.filter(|api_key| !api_key.is_empty());
+        self
+    }
+
+    /// Sets the key identifying this node. Submitted proofs are attested with
+    /// a signature over `(task_id, proof_hash, node_id)`, see
+    /// [`NodeKey::attest`].
+    pub fn with_node_key(mut self, node_key: Arc<NodeKey>) -> Self {
+        self.node_key = Some(node_key);
+        self
+    }
+
+    /// Signs every request with the node key, which must be set with
+    /// [`Self::with_node_key`].
+    ///
+    /// The signature covers the method, path, query string, a millisecond
+    /// timestamp, a random nonce and the protobuf body, each followed by a
+    /// newline except the body. The path is the full percent-encoded path of
+    /// the request URL, including any path in the orchestrator's base URL.
+    /// The query string is form-urlencoded in the order the parameters were
+    /// added, exactly as sent, and empty if there are none. The orchestrator rejects stale timestamps and reused nonces,
+    /// so a captured request cannot be replayed.
+    pub fn with_request_signing(mut self, sign_requests: bool) -> Self {
+        self.sign_requests = sign_requests;
+        self
+    }
+
+    /// Compresses submitted proofs with `codec`. If the orchestrator answers
+    /// 415 Unsupported Media Type, the proof is resent uncompressed and
+    /// compression is turned off for the rest of the session.
+    pub fn with_proof_codec(mut self, codec: ProofCodec) -> Self {
+        self.proof_codec = codec;
+        self
+    }
+
+    /// Sets the location reported in telemetry, which must already have been
+    /// validated with [`crate::location::validate_location`].
+    pub fn with_location(mut self, location: Option<String>) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Sets the measured capabilities reported in telemetry.
+    pub fn with_capabilities(mut self, capabilities: SharedCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Sends a request, retrying transient failures according to the client's
+    /// [`RetryPolicy`].
+    async fn execute<U>(&self, request: &ApiRequest<U>) -> Result<Option<U>, OrchestratorError>
+    where
+        U: Message + Default,
+    {
+        let mut retry = 0;
+        loop {
+            match self.send_once(request).await {
+                Err(e)
+                    if retry + 1 < self.retry_policy.max_attempts
+                        && e.is_retryable(request.idempotent) =>
+                {
+                    let Some(delay) = self.retry_policy.delay_for(retry, &e) else {
+                        return Err(e);
+                    };
+                    retry += 1;
+                    println!(
+                        "\tNexus Orchestrator: {} (retry {}/{} in {:.1}s)",
+                        e,
+                        retry,
+                        self.retry_policy.max_attempts - 1,
+                        delay.as_secs_f64()
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
         }
     }
 
-    // Added better error handling for Protobuf encoding
-    async fn make_request<T, U>(
-        &self,
-        url: &str,
-        method: &str,
-        request_data: &T,
-    ) -> Result<Option<U>, Box<dyn std::error::Error>>
+    async fn send_once<U>(&self, request: &ApiRequest<U>) -> Result<Option<U>, OrchestratorError>
     where
-        T: Message,
         U: Message + Default,
     {
-        let request_bytes = request_data.encode_to_vec();
-        let url = format!("{}{}", self.base_url, url);
+        let url = format!("{}{}", self.base_url, request.path);
+        if request.body.is_some() && !request.method.allows_body() {
+            return Err(OrchestratorError::Validation(format!(
+                "{:?} {} cannot have a body",
+                request.method, request.path
+            )));
+        }
 
-        let response = match method {
-            "POST" => self.client.post(&url),
-            "GET" => self.client.get(&url),
-            _ => return Err("Unsupported HTTP method".into()),
-        };
+        // Encode the URL once, so the signed path and query are exactly the
+        // ones sent.
+        // parse_with_params alone would leave a trailing "?" for an empty query.
+        let full_url = if request.query.is_empty() {
+            reqwest::Url::parse(&url)
+        } else {
+            reqwest::Url::parse_with_params(&url, &request.query)
+        }
+        .map_err(|e| OrchestratorError::Validation(format!("invalid URL {}: {}", url, e)))?;
+        let path = full_url.path().to_string();
+        let query = full_url.query().unwrap_or_default().to_string();
 
-        let response = response
-            .header("Content-Type", "application/octet-stream")
-            .body(request_bytes)
+        let mut builder = self.client.request(request.method.into(), full_url);
+        if let Some(api_key) = &self.api_key {
+            builder = builder.bearer_auth(api_key);
+        }
+        if let Some(signing_key) = self.node_key.as_ref().filter(|_| self.sign_requests) {
+            // Signed per attempt, so retries get a fresh timestamp and nonce
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or_default()
+                .to_string();
+            let nonce = hex::encode(rand::random::<[u8; 16]>());
+            let mut message = format!(
+                "{}\n{}\n{}\n{}\n{}\n",
+                reqwest::Method::from(request.method),
+                path,
+                query,
+                timestamp,
+                nonce
+            )
+            .into_bytes();
+            message.extend_from_slice(request.body.as_deref().unwrap_or_default());
+            builder = builder
+                .header(PUBLIC_KEY_HEADER, signing_key.public_key_hex())
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(NONCE_HEADER, nonce)
+                .header(
+                    SIGNATURE_HEADER,
+                    hex::encode(signing_key.sign(&message).to_bytes()),
+                );
+        }
+        if let Some(body) = &request.body {
+            builder = builder
+                .header(CONTENT_TYPE, "application/octet-stream")
+                .body(body.clone());
+        }
+        let response = builder
             .send()
             .await
-            .map_err(|_| "Failed to connect to orchestrator")?;
+            .map_err(|source| OrchestratorError::Transport {
+                url: url.clone(),
+                source,
+            })?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await?;
-            return Err(format!("HTTP {}: {}", status, error_text).into());
+        let status = response.status();
+        if !request.accepts(status) {
+            // Only the delta-seconds form of Retry-After is supported
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let body = response.text().await.unwrap_or_default();
+            return Err(if status.is_success() {
+                OrchestratorError::UnexpectedStatus { url, status, body }
+            } else {
+                OrchestratorError::from_status(url, status, body, retry_after)
+            });
         }
 
-        let response_bytes = response.bytes().await?;
+        let response_bytes =
+            response
+                .bytes()
+                .await
+                .map_err(|source| OrchestratorError::Transport {
+                    url: url.clone(),
+                    source,
+                })?;
         if response_bytes.is_empty() {
             return Ok(None);
         }
 
         U::decode(response_bytes)
             .map(Some)
-            .map_err(|e| format!("Protobuf decode error: {}", e).into())
+            .map_err(|source| OrchestratorError::Decode { url, source })
     }
 
     // Added input validation
     pub async fn get_proof_task(
         &self,
         node_id: &str,
-    ) -> Result<GetProofTaskResponse, Box<dyn std::error::Error>> {
+    ) -> Result<GetProofTaskResponse, OrchestratorError> {
         if node_id.is_empty() {
-            return Err("Invalid node ID".into());
+            return Err(OrchestratorError::Validation("empty node ID".into()));
         }
 
         let request = GetProofTaskRequest {
@@ -77,42 +745,397 @@ impl OrchestratorClient {
             node_type: NodeType::CliProver as i32,
         };
 
-        self.make_request("/tasks", "POST", &request)
+        // Fetching a task only assigns one, so it is safe to retry
+        let api_request = ApiRequest::<GetProofTaskResponse>::post("/tasks")
+            .body(&request)
+            .idempotent(true);
+        let url = format!("{}/tasks", self.base_url);
+        let task = self
+            .execute(&api_request)
             .await?
-            .ok_or_else(|| "Empty response from orchestrator".into())
+            .ok_or_else(|| OrchestratorError::EmptyResponse { url: url.clone() })?;
+        // Without an ID the proof could never be submitted, so reject the task
+        // before spending minutes proving it
+        if task.task_id.is_empty() {
+            return Err(OrchestratorError::InvalidTask {
+                url,
+                reason: "empty task ID".into(),
+            });
+        }
+        Ok(task)
     }
 
     // Added proof validation before submission
     pub async fn submit_proof(
         &self,
         node_id: &str,
+        task_id: &str,
         proof_hash: &str,
         proof: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        metrics: Option<&ProofMetrics>,
+    ) -> Result<(), OrchestratorError> {
         if proof.is_empty() {
-            return Err("Empty proof submitted".into());
+            return Err(OrchestratorError::Validation("empty proof".into()));
+        }
+        if task_id.is_empty() {
+            return Err(OrchestratorError::Validation("empty task ID".into()));
         }
 
-        let (program_memory, total_memory) = get_memory_info();
-        let flops = measure_flops();
+        let capabilities = self
+            .capabilities
+            .as_ref()
+            .map(|capabilities| capabilities.read().unwrap().clone());
 
-        let request = SubmitProofRequest {
+        let mut request = SubmitProofRequest {
             node_id: node_id.to_string(),
             node_type: NodeType::CliProver as i32,
+            task_id: task_id.to_string(),
             proof_hash: proof_hash.to_string(),
-            proof,
-            node_telemetry: Some(crate::nexus_orchestrator::NodeTelemetry {
-                flops_per_sec: Some(flops as i32),
-                memory_used: Some(program_memory),
-                memory_capacity: Some(total_memory),
-                location: Some("US".to_string()),
-            }),
+            proof: Vec::new(),
+            proof_encoding: ProofEncoding::Identity as i32,
+            node_telemetry: Some(telemetry::node_telemetry(
+                capabilities.as_ref(),
+                self.location.clone(),
+                metrics,
+            )),
+            ed25519_public_key: self
+                .node_key
+                .as_ref()
+                .map(|key| key.public_key_bytes().to_vec())
+                .unwrap_or_default(),
+            signature: self
+                .node_key
+                .as_ref()
+                .map(|key| key.attest(task_id, proof_hash, node_id).to_bytes().to_vec())
+                .unwrap_or_default(),
         };
 
-        self.make_request::<SubmitProofRequest, ()>("/tasks/submit", "POST", &request)
-            .await?;
+        let (proof, uncompressed) = self.compress_proof(proof).await;
+        request.proof = proof;
+        if uncompressed.is_some() {
+            request.proof_encoding = self.proof_codec.encoding() as i32;
+        }
+
+        let result = self.send_submission(&mut request).await;
+        match (result, uncompressed) {
+            (Err(e), Some(uncompressed))
+                if e.status() == Some(StatusCode::UNSUPPORTED_MEDIA_TYPE) =>
+            {
+                println!(
+                    "\tNexus Orchestrator: {} proofs not accepted, sending proofs uncompressed",
+                    self.proof_codec
+                );
+                self.compression_rejected.store(true, Ordering::Relaxed);
+                request.proof = uncompressed;
+                request.proof_encoding = ProofEncoding::Identity as i32;
+                self.send_submission(&mut request).await?;
+            }
+            (result, _) => result?,
+        }
 
         println!("\tNexus Orchestrator: Proof submitted successfully");
         Ok(())
     }
+
+    /// Submits `request` in one go, or as a chunked upload if its proof is
+    /// larger than the configured threshold.
+    async fn send_submission(
+        &self,
+        request: &mut SubmitProofRequest,
+    ) -> Result<(), OrchestratorError> {
+        if request.proof.len() <= self.chunked_upload_threshold {
+            self.execute(&ApiRequest::<()>::post("/tasks/submit").body(&*request))
+                .await?;
+            return Ok(());
+        }
+
+        // The proof travels in the chunks; the finalize request carries the rest
+        let proof = std::mem::take(&mut request.proof);
+        let result = self.submit_proof_chunked(request, &proof).await;
+        request.proof = proof;
+        result
+    }
+
+    /// Uploads `proof` in chunks, then finalizes the submission described by
+    /// `request`.
+    ///
+    /// The orchestrator identifies an upload by task ID and proof hash, so
+    /// initiating an upload it already holds part of, whether after a dropped
+    /// connection or from the outbox after a restart, resumes it from the last
+    /// committed offset.
+    async fn submit_proof_chunked(
+        &self,
+        request: &SubmitProofRequest,
+        proof: &[u8],
+    ) -> Result<(), OrchestratorError> {
+        let initiate = InitiateProofUploadRequest {
+            node_id: request.node_id.clone(),
+            node_type: request.node_type,
+            task_id: request.task_id.clone(),
+            proof_hash: request.proof_hash.clone(),
+            total_size: proof.len() as u64,
+            proof_encoding: request.proof_encoding,
+        };
+
+        let mut resumes = 0;
+        let upload_id = loop {
+            // Initiating an upload never creates a second one for the same proof
+            let api_request = ApiRequest::<InitiateProofUploadResponse>::post("/tasks/upload")
+                .body(&initiate)
+                .idempotent(true);
+            let upload = self.execute(&api_request).await?.ok_or_else(|| {
+                OrchestratorError::EmptyResponse {
+                    url: format!("{}/tasks/upload", self.base_url),
+                }
+            })?;
+            if upload.committed_offset > 0 {
+                println!(
+                    "\tResuming proof upload at {} of {} bytes",
+                    upload.committed_offset,
+                    proof.len()
+                );
+            }
+
+            match self
+                .upload_chunks(&upload.upload_id, proof, upload.committed_offset)
+                .await
+            {
+                Ok(()) => break upload.upload_id,
+                Err(e) if e.is_transient() && resumes < MAX_UPLOAD_RESUMES => {
+                    resumes += 1;
+                    println!(
+                        "\tProof upload interrupted: {} (resume {}/{})",
+                        e, resumes, MAX_UPLOAD_RESUMES
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        let finalize = FinalizeProofUploadRequest {
+            upload_id: upload_id.clone(),
+            submission: Some(request.clone()),
+        };
+        // Finalizing is keyed by upload ID, so a repeated finalize is a no-op
+        let api_request = ApiRequest::<()>::post(format!("/tasks/upload/{}/finalize", upload_id))
+            .body(&finalize)
+            .idempotent(true);
+        self.execute(&api_request).await?;
+        println!(
+            "\tUploaded proof in chunks of {} bytes",
+            self.upload_chunk_size
+        );
+        Ok(())
+    }
+
+    /// Uploads `proof` from `offset` onwards, each chunk carrying its offset
+    /// and Keccak-256 hash.
+    async fn upload_chunks(
+        &self,
+        upload_id: &str,
+        proof: &[u8],
+        mut offset: u64,
+    ) -> Result<(), OrchestratorError> {
+        use sha3::{Digest, Keccak256};
+
+        let path = format!("/tasks/upload/{}", upload_id);
+        while (offset as usize) < proof.len() {
+            let start = offset as usize;
+            let end = (start + self.upload_chunk_size).min(proof.len());
+            let data = &proof[start..end];
+            let chunk = UploadProofChunkRequest {
+                upload_id: upload_id.to_string(),
+                offset,
+                chunk_hash: format!("{:x}", Keccak256::digest(data)),
+                data: data.to_vec(),
+            };
+
+            // Writing the same bytes at the same offset twice is harmless
+            let api_request = ApiRequest::<UploadProofChunkResponse>::put(path.as_str())
+                .query("offset", offset)
+                .body(&chunk);
+            let committed = self
+                .execute(&api_request)
+                .await?
+                .ok_or_else(|| OrchestratorError::EmptyResponse {
+                    url: format!("{}{}", self.base_url, path),
+                })?
+                .committed_offset;
+            if committed <= offset {
+                return Err(OrchestratorError::UploadStalled {
+                    url: format!("{}{}", self.base_url, path),
+                    offset,
+                });
+            }
+            offset = committed;
+        }
+        Ok(())
+    }
+
+    /// Compresses `proof` with the configured codec on the blocking thread
+    /// pool. Returns the proof to send, plus the uncompressed proof if it was
+    /// compressed, to fall back on if the orchestrator rejects the codec.
+    async fn compress_proof(&self, proof: Vec<u8>) -> (Vec<u8>, Option<Vec<u8>>) {
+        let codec = self.proof_codec;
+        if codec == ProofCodec::None || self.compression_rejected.load(Ordering::Relaxed) {
+            return (proof, None);
+        }
+
+        let proof = Arc::new(proof);
+        let compressed = {
+            let proof = proof.clone();
+            tokio::task::spawn_blocking(move || codec.compress(&proof)).await
+        };
+        let proof = Arc::try_unwrap(proof).unwrap_or_else(|proof| proof.as_ref().clone());
+        match compressed {
+            Ok(Ok(compressed)) => {
+                println!(
+                    "\tCompressed proof with {}: {} -> {} bytes ({:.1}%)",
+                    codec,
+                    proof.len(),
+                    compressed.len(),
+                    100.0 * compressed.len() as f64 / proof.len() as f64
+                );
+                (compressed, Some(proof))
+            }
+            Ok(Err(e)) => {
+                println!("\tFailed to compress proof, sending it uncompressed: {}", e);
+                (proof, None)
+            }
+            Err(e) => {
+                println!("\tFailed to compress proof, sending it uncompressed: {}", e);
+                (proof, None)
+            }
+        }
+    }
+
+    /// Tells the orchestrator this node abandoned a task, so it can be
+    /// reassigned without waiting for it to time out.
+    pub async fn report_task_failure(
+        &self,
+        node_id: &str,
+        task_id: &str,
+        category: TaskFailureCategory,
+        exit_code: Option<u32>,
+        error: &str,
+    ) -> Result<(), OrchestratorError> {
+        if task_id.is_empty() {
+            return Err(OrchestratorError::Validation("empty task ID".into()));
+        }
+
+        let request = ReportTaskFailureRequest {
+            node_id: node_id.to_string(),
+            node_type: NodeType::CliProver as i32,
+            task_id: task_id.to_string(),
+            category: category as i32,
+            exit_code,
+            error: error.to_string(),
+        };
+
+        // Reporting the same failure twice is harmless
+        let api_request = ApiRequest::<()>::post("/tasks/failure")
+            .body(&request)
+            .idempotent(true);
+        self.execute(&api_request).await?;
+
+        println!("\tNexus Orchestrator: Task failure reported");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_error(status: StatusCode) -> OrchestratorError {
+        OrchestratorError::from_status(
+            "http://orchestrator/tasks".into(),
+            status,
+            String::new(),
+            None,
+        )
+    }
+
+    #[test]
+    fn rate_limiting_and_unavailability_are_always_retryable() {
+        for status in [
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            let error = status_error(status);
+            assert!(error.is_retryable(true), "{}", status);
+            assert!(error.is_retryable(false), "{}", status);
+        }
+    }
+
+    #[test]
+    fn gateway_errors_are_only_retryable_when_idempotent() {
+        for status in [StatusCode::BAD_GATEWAY, StatusCode::GATEWAY_TIMEOUT] {
+            let error = status_error(status);
+            assert!(error.is_retryable(true), "{}", status);
+            // The orchestrator may have accepted the submission behind the gateway
+            assert!(!error.is_retryable(false), "{}", status);
+        }
+    }
+
+    #[test]
+    fn client_and_internal_errors_are_never_retryable() {
+        for status in [
+            StatusCode::BAD_REQUEST,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::NOT_FOUND,
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ] {
+            let error = status_error(status);
+            assert!(!error.is_retryable(true), "{}", status);
+            assert!(!error.is_retryable(false), "{}", status);
+        }
+    }
+
+    #[test]
+    fn local_errors_are_never_retryable() {
+        let errors = [
+            OrchestratorError::Validation("empty proof".into()),
+            OrchestratorError::EmptyResponse {
+                url: "http://orchestrator/tasks".into(),
+            },
+        ];
+        for error in errors {
+            assert!(!error.is_retryable(true), "{}", error);
+            assert!(!error.is_retryable(false), "{}", error);
+        }
+    }
+
+    #[test]
+    fn long_retry_after_is_returned_to_the_caller() {
+        let policy = RetryPolicy::default();
+        let rate_limited = |retry_after| OrchestratorError::RateLimited {
+            url: "http://orchestrator/tasks".into(),
+            status: StatusCode::TOO_MANY_REQUESTS,
+            body: String::new(),
+            retry_after: Some(retry_after),
+        };
+        assert_eq!(
+            policy.delay_for(0, &rate_limited(Duration::from_secs(2))),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay_for(0, &rate_limited(Duration::from_secs(86400))),
+            None
+        );
+    }
+
+    #[test]
+    fn retry_policy_follows_client_config() {
+        let config = ClientConfig {
+            retry_max_attempts: 0,
+            retry_base_delay_ms: 250,
+            retry_max_delay_secs: 10,
+            ..ClientConfig::default()
+        };
+        let policy = config.retry_policy();
+        assert_eq!(policy.max_attempts, 1);
+        assert_eq!(policy.base_delay, Duration::from_millis(250));
+        assert_eq!(policy.max_delay, Duration::from_secs(10));
+    }
 }
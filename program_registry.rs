@@ -0,0 +1,257 @@
+use crate::public_input::InputSchema;
+use nexus_sdk::{stwo::seq::Stwo, Local, Prover};
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use thiserror::Error;
+
+/// Program proven when the orchestrator does not name one, and by anonymous nodes
+pub const DEFAULT_PROGRAM_ID: &str = "fib_input";
+
+/// Guest programs compiled into the binary, with the input they expect
+const EMBEDDED_PROGRAMS: &[(&str, &[u8], InputSchema)] = &[(
+    DEFAULT_PROGRAM_ID,
+    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/fib_input")),
+    InputSchema::U32,
+)];
+
+/// Extension of the file holding the expected Keccak-256 hash of an ELF in
+/// the programs directory, e.g. `fib_input.keccak256` for `fib_input`
+const HASH_FILE_EXTENSION: &str = "keccak256";
+
+#[derive(Debug, Error)]
+pub enum ProgramError {
+    #[error("unknown program {0}")]
+    UnknownProgram(String),
+
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("hash mismatch for program {program_id}: expected {expected}, found {actual}")]
+    HashMismatch {
+        program_id: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("failed to load program {program_id}: {message}")]
+    Load { program_id: String, message: String },
+}
+
+enum ProgramSource {
+    Embedded(&'static [u8]),
+    File(PathBuf),
+}
+
+struct ProgramEntry {
+    source: ProgramSource,
+    /// Hex Keccak-256 hash the ELF must match before it is loaded
+    expected_hash: Option<String>,
+    input_schema: InputSchema,
+}
+
+impl ProgramEntry {
+    /// Identifies the current version of a program file, so cached programs
+    /// are reloaded when the file changes. Embedded programs never change.
+    fn version(&self) -> Result<Option<(SystemTime, u64)>, ProgramError> {
+        match &self.source {
+            ProgramSource::Embedded(_) => Ok(None),
+            ProgramSource::File(path) => {
+                let metadata = std::fs::metadata(path).map_err(|source| ProgramError::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                let modified = metadata.modified().map_err(|source| ProgramError::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                Ok(Some((modified, metadata.len())))
+            }
+        }
+    }
+}
+
+/// A parsed and verified program, kept so it is only loaded once
+struct CachedProgram {
+    version: Option<(SystemTime, u64)>,
+    prover: Stwo<Local>,
+}
+
+/// Maps program ids from orchestrator tasks to guest ELFs.
+///
+/// Programs embedded in the binary are always available; programs found in a
+/// programs directory override embedded ones with the same id. Programs are
+/// parsed on first use and cached until their file changes.
+pub struct ProgramRegistry {
+    programs: HashMap<String, ProgramEntry>,
+    cache: Mutex<HashMap<String, CachedProgram>>,
+}
+
+impl Default for ProgramRegistry {
+    fn default() -> Self {
+        let programs = EMBEDDED_PROGRAMS
+            .iter()
+            .map(|(id, elf, input_schema)| {
+                let entry = ProgramEntry {
+                    source: ProgramSource::Embedded(elf),
+                    expected_hash: None,
+                    input_schema: *input_schema,
+                };
+                (id.to_string(), entry)
+            })
+            .collect();
+        Self {
+            programs,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl ProgramRegistry {
+    /// The default programs directory, `~/.nexus/programs`.
+    pub fn default_dir() -> Option<PathBuf> {
+        home::home_dir().map(|home| home.join(".nexus").join("programs"))
+    }
+
+    /// Registers every ELF in `dir` under its file stem, along with its expected
+    /// hash from the matching `.keccak256` file. Files without a hash file are
+    /// skipped, so a program is never proved without its hash being checked.
+    ///
+    /// Programs replacing an embedded program keep its input schema; other
+    /// programs receive their public inputs as raw bytes.
+    pub fn load_directory(&mut self, dir: &Path) -> Result<usize, ProgramError> {
+        let io_error = |source| ProgramError::Io {
+            path: dir.to_path_buf(),
+            source,
+        };
+
+        let mut count = 0;
+        for dir_entry in std::fs::read_dir(dir).map_err(io_error)? {
+            let path = dir_entry.map_err(io_error)?.path();
+            if !path.is_file()
+                || path.extension().and_then(|ext| ext.to_str()) == Some(HASH_FILE_EXTENSION)
+            {
+                continue;
+            }
+            let Some(program_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            let hash_path = path.with_extension(HASH_FILE_EXTENSION);
+            if !hash_path.exists() {
+                println!(
+                    "Skipping {}: no {} file with its expected hash",
+                    path.display(),
+                    hash_path.display()
+                );
+                continue;
+            }
+            let hash = std::fs::read_to_string(&hash_path).map_err(|source| ProgramError::Io {
+                path: hash_path.clone(),
+                source,
+            })?;
+            let expected_hash = Some(hash.trim().to_lowercase());
+
+            let input_schema = self
+                .programs
+                .get(program_id)
+                .map(|entry| entry.input_schema)
+                .unwrap_or(InputSchema::Bytes);
+            self.register_file(program_id, path.clone(), expected_hash, input_schema);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub fn register_file(
+        &mut self,
+        program_id: &str,
+        path: PathBuf,
+        expected_hash: Option<String>,
+        input_schema: InputSchema,
+    ) {
+        self.cache.get_mut().unwrap().remove(program_id);
+        self.programs.insert(
+            program_id.to_string(),
+            ProgramEntry {
+                source: ProgramSource::File(path),
+                expected_hash,
+                input_schema,
+            },
+        );
+    }
+
+    /// How the public inputs of tasks for `program_id` are encoded.
+    pub fn input_schema(&self, program_id: &str) -> Result<InputSchema, ProgramError> {
+        self.programs
+            .get(program_id)
+            .map(|entry| entry.input_schema)
+            .ok_or_else(|| ProgramError::UnknownProgram(program_id.to_string()))
+    }
+
+    /// Reads the ELF registered for `program_id`, verifying its hash if one is known.
+    pub fn elf_bytes(&self, program_id: &str) -> Result<Vec<u8>, ProgramError> {
+        let entry = self
+            .programs
+            .get(program_id)
+            .ok_or_else(|| ProgramError::UnknownProgram(program_id.to_string()))?;
+
+        let bytes = match &entry.source {
+            ProgramSource::Embedded(elf) => elf.to_vec(),
+            ProgramSource::File(path) => {
+                std::fs::read(path).map_err(|source| ProgramError::Io {
+                    path: path.clone(),
+                    source,
+                })?
+            }
+        };
+
+        if let Some(expected) = &entry.expected_hash {
+            let actual = format!("{:x}", Keccak256::digest(&bytes));
+            if &actual != expected {
+                return Err(ProgramError::HashMismatch {
+                    program_id: program_id.to_string(),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Creates a Stwo prover for the program registered as `program_id`,
+    /// reusing the parsed program if its ELF has not changed since last use.
+    pub fn load(&self, program_id: &str) -> Result<Stwo<Local>, ProgramError> {
+        let load_error = |e: &dyn std::fmt::Display| ProgramError::Load {
+            program_id: program_id.to_string(),
+            message: e.to_string(),
+        };
+
+        let entry = self
+            .programs
+            .get(program_id)
+            .ok_or_else(|| ProgramError::UnknownProgram(program_id.to_string()))?;
+        let version = entry.version()?;
+
+        if let Some(cached) = self.cache.lock().unwrap().get(program_id) {
+            if cached.version == version {
+                return Stwo::<Local>::new(&cached.prover.elf).map_err(|e| load_error(&e));
+            }
+        }
+
+        let bytes = self.elf_bytes(program_id)?;
+        let prover = Stwo::<Local>::new_from_bytes(&bytes).map_err(|e| load_error(&e))?;
+        let fresh = Stwo::<Local>::new(&prover.elf).map_err(|e| load_error(&e))?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(program_id.to_string(), CachedProgram { version, prover });
+        Ok(fresh)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::nexus_orchestrator::ProofEncoding;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// zstd level; higher levels barely shrink proofs further but cost seconds
+const ZSTD_LEVEL: i32 = 3;
+
+/// Compression applied to the `proof` field of a submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofCodec {
+    #[default]
+    None,
+    Zstd,
+    Gzip,
+}
+
+impl ProofCodec {
+    /// Compresses a serialized proof. Runs for a while on large proofs and
+    /// should not be called on the async runtime.
+    pub fn compress(self, proof: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(proof.to_vec()),
+            Self::Zstd => zstd::encode_all(proof, ZSTD_LEVEL),
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(proof)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    /// How the codec is advertised in `SubmitProofRequest::proof_encoding`.
+    pub fn encoding(self) -> ProofEncoding {
+        match self {
+            Self::None => ProofEncoding::Identity,
+            Self::Zstd => ProofEncoding::Zstd,
+            Self::Gzip => ProofEncoding::Gzip,
+        }
+    }
+}
+
+impl FromStr for ProofCodec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" | "" => Ok(Self::None),
+            "zstd" => Ok(Self::Zstd),
+            "gzip" => Ok(Self::Gzip),
+            other => Err(format!(
+                "unknown proof compression {:?}: expected none, zstd or gzip",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ProofCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Zstd => "zstd",
+            Self::Gzip => "gzip",
+        })
+    }
+}
@@ -0,0 +1,147 @@
+use crate::prover::proof_hash;
+use crate::public_input::{InputSchema, PublicInput, StwoProof};
+use colored::Colorize;
+use nexus_sdk::{stwo::seq::Stwo, Local, Prover, Verifiable, Viewable};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+/// Prove a guest program once, outside of the orchestrator loop
+#[derive(clap::Args, Debug, Clone)]
+pub struct ProveArgs {
+    /// Guest program ELF
+    #[arg(long)]
+    pub elf: PathBuf,
+
+    /// Public input: comma-separated u32 values such as `9` or `3,4`, or a
+    /// file holding the input bytes as sent by the orchestrator
+    #[arg(long)]
+    pub input: String,
+
+    /// How the guest expects its input
+    #[arg(long, value_enum, default_value = "u32")]
+    pub schema: InputSchema,
+
+    /// Where to write the bincode-serialized proof
+    #[arg(long, short, default_value = "proof.bin")]
+    pub output: PathBuf,
+}
+
+/// Verify a proof file written by `prove` or submitted by a node
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyArgs {
+    /// Guest program ELF the proof claims to be for
+    #[arg(long)]
+    pub elf: PathBuf,
+
+    /// Bincode-serialized proof
+    #[arg(long)]
+    pub proof: PathBuf,
+
+    /// Public input the program was proved with, in the same forms as for `prove`
+    #[arg(long)]
+    pub input: String,
+
+    /// How the guest expects its input
+    #[arg(long, value_enum, default_value = "u32")]
+    pub schema: InputSchema,
+}
+
+/// Decodes `--input`, either as literal u32 values or as the contents of a file.
+fn parse_input(input: &str, schema: InputSchema) -> Result<PublicInput, Box<dyn Error>> {
+    let path = Path::new(input);
+    let bytes = if path.is_file() {
+        std::fs::read(path)?
+    } else {
+        input
+            .split(',')
+            .map(|value| value.trim().parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("invalid input {:?}: {}", input, e))?
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    };
+    Ok(schema.decode(&bytes)?)
+}
+
+fn load_prover(elf: &Path) -> Result<Stwo<Local>, Box<dyn Error>> {
+    let bytes =
+        std::fs::read(elf).map_err(|e| format!("failed to read {}: {}", elf.display(), e))?;
+    Stwo::<Local>::new_from_bytes(&bytes)
+        .map_err(|e| format!("failed to load {}: {}", elf.display(), e).into())
+}
+
+/// Runs the `prove` subcommand. The proof is written even if the guest exits
+/// with an error, but the command then fails with the guest's exit code shown.
+pub fn run_prove(args: &ProveArgs) -> Result<ExitCode, Box<dyn Error>> {
+    let public_input = parse_input(&args.input, args.schema)?;
+
+    let load_started = Instant::now();
+    let prover = load_prover(&args.elf)?;
+    let load_time = load_started.elapsed();
+
+    println!(
+        "Proving {} with input {}...",
+        args.elf.display(),
+        public_input
+    );
+    let prove_started = Instant::now();
+    let (view, proof) = public_input.prove(prover)?;
+    let prove_time = prove_started.elapsed();
+
+    let exit_code = view.exit_code()?;
+    for line in view.logs().unwrap_or_default() {
+        println!("\t[guest] {}", line);
+    }
+
+    let proof_bytes = bincode::serialize(&proof)?;
+    std::fs::write(&args.output, &proof_bytes)
+        .map_err(|e| format!("failed to write {}: {}", args.output.display(), e))?;
+
+    println!("{}: {}", "Exit code".bold(), exit_code);
+    println!("{}: {:.2?}", "Load time".bold(), load_time);
+    println!("{}: {:.2?}", "Proving time".bold(), prove_time);
+    println!("{}: {} bytes", "Proof size".bold(), proof_bytes.len());
+    println!("{}: {}", "Proof hash".bold(), proof_hash(&proof_bytes));
+    println!("{}: {}", "Proof written to".bold(), args.output.display());
+
+    Ok(if exit_code == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Runs the `verify` subcommand. The guest is executed with the given input
+/// first, and the proof must match that run's exit code and public output.
+pub fn run_verify(args: &VerifyArgs) -> Result<ExitCode, Box<dyn Error>> {
+    let public_input = parse_input(&args.input, args.schema)?;
+    let prover = load_prover(&args.elf)?;
+    let proof_bytes = std::fs::read(&args.proof)
+        .map_err(|e| format!("failed to read {}: {}", args.proof.display(), e))?;
+
+    println!("{}: {}", "Proof hash".bold(), proof_hash(&proof_bytes));
+    let proof: StwoProof = bincode::deserialize(&proof_bytes)
+        .map_err(|e| format!("{} is not a valid proof: {}", args.proof.display(), e))?;
+
+    let view = public_input
+        .run(&prover)
+        .map_err(|e| format!("failed to run {}: {}", args.elf.display(), e))?;
+    println!("{}: {}", "Exit code".bold(), view.exit_code()?);
+
+    let verify_started = Instant::now();
+    let result = proof.verify(&view);
+    let verify_time = verify_started.elapsed();
+    match result {
+        Ok(()) => {
+            println!("{} in {:.2?}", "Proof is valid".green(), verify_time);
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(e) => {
+            println!("{}: {}", "Proof is invalid".red(), e);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use crate::orchestrator_client::OrchestratorClient;
+use crate::task_state::{TaskLifecycle, TaskState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A proof that has been computed but not yet acknowledged by the orchestrator.
+/// The proof itself is stored after the entry in the same file and only read
+/// when it is submitted, see [`ProofOutbox::load_proof`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub node_id: String,
+    pub lifecycle: TaskLifecycle,
+    pub proof_hash: String,
+    /// Seconds since the Unix epoch at which the entry was stored.
+    pub created_at: u64,
+}
+
+/// Identifies a submission in the outbox. Stwo proofs are deterministic, so
+/// two tasks with the same program and input share a proof hash; keying by
+/// task ID as well keeps them separate submissions.
+pub fn entry_key(task_id: &str, proof_hash: &str) -> String {
+    // Task IDs come from the orchestrator; keep them safe as file names
+    let task_id: String = task_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-{}", task_id, proof_hash)
+}
+
+impl OutboxEntry {
+    pub fn key(&self) -> String {
+        entry_key(&self.lifecycle.task_id, &self.proof_hash)
+    }
+
+    pub fn new(node_id: &str, lifecycle: TaskLifecycle, proof_hash: &str) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self {
+            node_id: node_id.to_string(),
+            lifecycle,
+            proof_hash: proof_hash.to_string(),
+            created_at,
+        }
+    }
+}
+
+/// On-disk queue of proofs awaiting submission, one file per submission named
+/// after its [`entry_key`], so the same submission is never stored (or sent)
+/// twice.
+pub struct ProofOutbox {
+    dir: PathBuf,
+    /// Keys of entries currently being submitted, which the drainer must not
+    /// pick up.
+    in_flight: Mutex<HashSet<String>>,
+}
+
+impl ProofOutbox {
+    /// The default outbox location, `~/.nexus/outbox`.
+    pub fn default_dir() -> Option<PathBuf> {
+        home::home_dir().map(|home| home.join(".nexus").join("outbox"))
+    }
+
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            in_flight: Mutex::new(HashSet::new()),
+        })
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.bin", key))
+    }
+
+    /// Marks an entry as in flight and persists it. Returns `false` if the
+    /// same submission is already stored or being submitted, in which case
+    /// the caller must leave it to the outbox.
+    pub fn push(&self, entry: &OutboxEntry, proof: &[u8]) -> io::Result<bool> {
+        // Claim the key before the file appears, so the drainer cannot pick
+        // it up and submit it concurrently
+        let key = entry.key();
+        if !self.in_flight.lock().unwrap().insert(key.clone()) {
+            return Ok(false);
+        }
+
+        let path = self.entry_path(&key);
+        if path.exists() {
+            self.release(&key);
+            return Ok(false);
+        }
+
+        let written = bincode::serialize(entry)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            .and_then(|mut bytes| {
+                bytes.extend_from_slice(proof);
+                // Write then rename so a crash never leaves a truncated entry behind
+                let tmp_path = path.with_extension("tmp");
+                std::fs::write(&tmp_path, bytes)?;
+                std::fs::rename(&tmp_path, &path)
+            });
+        if let Err(e) = written {
+            self.release(&key);
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// Deletes an entry once the orchestrator has acknowledged it.
+    pub fn remove(&self, key: &str) -> io::Result<()> {
+        self.in_flight.lock().unwrap().remove(key);
+        match std::fs::remove_file(self.entry_path(key)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Hands an entry whose submission failed over to the drainer.
+    pub fn release(&self, key: &str) {
+        self.in_flight.lock().unwrap().remove(key);
+    }
+
+    /// Reads the proof stored with `entry`.
+    pub fn load_proof(&self, entry: &OutboxEntry) -> io::Result<Vec<u8>> {
+        let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+        let header_len = bincode::serialized_size(entry).map_err(invalid)? as usize;
+        let mut bytes = std::fs::read(self.entry_path(&entry.key()))?;
+        if bytes.len() < header_len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "outbox entry is truncated",
+            ));
+        }
+        Ok(bytes.split_off(header_len))
+    }
+
+    /// Loads the metadata of all stored entries, oldest first, without their
+    /// proofs. Unreadable entries are skipped.
+    pub fn pending(&self) -> io::Result<Vec<OutboxEntry>> {
+        let mut entries = Vec::new();
+        for dir_entry in std::fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("bin") {
+                continue;
+            }
+            match std::fs::File::open(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|file| {
+                    bincode::deserialize_from::<_, OutboxEntry>(io::BufReader::new(file))
+                        .map_err(|e| e.to_string())
+                }) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => println!("\tOutbox: skipping {}: {}", path.display(), e),
+            }
+        }
+        entries.sort_by_key(|entry| entry.created_at);
+        Ok(entries)
+    }
+
+    /// Re-submits every stored entry that is not already in flight. Entries
+    /// are only deleted once submitted or definitively rejected. Stops at the
+    /// first other failure, transient or fatal (such as a wrong API key),
+    /// since later entries would fail the same way. Returns the number of
+    /// entries submitted.
+    pub async fn drain(&self, client: &OrchestratorClient) -> usize {
+        let entries = match self.pending() {
+            Ok(entries) => entries,
+            Err(e) => {
+                println!("\tOutbox: failed to read pending proofs: {}", e);
+                return 0;
+            }
+        };
+
+        let mut submitted = 0;
+        for mut entry in entries {
+            let key = entry.key();
+            if !self.in_flight.lock().unwrap().insert(key.clone()) {
+                continue;
+            }
+
+            let proof = match self.load_proof(&entry) {
+                Ok(proof) => proof,
+                Err(e) => {
+                    println!("\tOutbox: skipping {}: {}", key, e);
+                    self.release(&key);
+                    continue;
+                }
+            };
+
+            entry.lifecycle.advance(TaskState::Submitting);
+            match client
+                .submit_proof(
+                    &entry.node_id,
+                    &entry.lifecycle.task_id,
+                    &entry.proof_hash,
+                    proof,
+                    None,
+                )
+                .await
+            {
+                Ok(()) => {
+                    entry.lifecycle.advance(TaskState::Submitted);
+                    submitted += 1;
+                    if let Err(e) = self.remove(&key) {
+                        println!("\tOutbox: failed to remove {}: {}", key, e);
+                    }
+                }
+                Err(e) if !e.is_rejection() => {
+                    entry.lifecycle.advance(TaskState::Proved);
+                    self.release(&key);
+                    if !e.is_transient() {
+                        println!("\tOutbox: keeping pending proofs: {}", e);
+                    }
+                    break;
+                }
+                Err(e) => {
+                    // The orchestrator will never accept this proof
+                    entry.lifecycle.advance(TaskState::Failed);
+                    println!("\tOutbox: dropping proof {}: {}", entry.proof_hash, e);
+                    if let Err(e) = self.remove(&key) {
+                        println!("\tOutbox: failed to remove {}: {}", key, e);
+                    }
+                }
+            }
+        }
+        submitted
+    }
+}
+
+/// Periodically drains the outbox in the background, starting immediately so
+/// proofs left over from a previous run are submitted first.
+pub fn spawn_drainer(
+    outbox: Arc<ProofOutbox>,
+    client: Arc<OrchestratorClient>,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let submitted = outbox.drain(&client).await;
+            if submitted > 0 {
+                println!("\tOutbox: submitted {} pending proof(s)", submitted);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty outbox in a fresh temporary directory
+    fn temp_outbox(name: &str) -> ProofOutbox {
+        let dir =
+            std::env::temp_dir().join(format!("nexus-outbox-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        ProofOutbox::open(dir).unwrap()
+    }
+
+    fn entry(task_id: &str, proof_hash: &str) -> OutboxEntry {
+        OutboxEntry::new("node", TaskLifecycle::fetched(task_id), proof_hash)
+    }
+
+    #[test]
+    fn push_stores_entry_and_proof() {
+        let outbox = temp_outbox("push");
+        let stored = entry("task-1", "abc");
+        assert!(outbox.push(&stored, &[1, 2, 3]).unwrap());
+
+        let pending = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].key(), stored.key());
+        assert_eq!(outbox.load_proof(&pending[0]).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn push_rejects_the_same_submission_twice() {
+        let outbox = temp_outbox("duplicate");
+        let stored = entry("task-1", "abc");
+        assert!(outbox.push(&stored, &[1]).unwrap());
+        // Still in flight
+        assert!(!outbox.push(&stored, &[1]).unwrap());
+        // Released to the drainer, but still stored
+        outbox.release(&stored.key());
+        assert!(!outbox.push(&stored, &[1]).unwrap());
+        assert_eq!(outbox.pending().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn same_proof_for_different_tasks_is_stored_twice() {
+        let outbox = temp_outbox("collision");
+        assert!(outbox.push(&entry("task-1", "abc"), &[1]).unwrap());
+        assert!(outbox.push(&entry("task-2", "abc"), &[1]).unwrap());
+        assert_eq!(outbox.pending().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn remove_deletes_entry_and_clears_claim() {
+        let outbox = temp_outbox("remove");
+        let stored = entry("task-1", "abc");
+        outbox.push(&stored, &[1]).unwrap();
+        outbox.remove(&stored.key()).unwrap();
+        assert!(outbox.pending().unwrap().is_empty());
+        // Removing twice is not an error
+        outbox.remove(&stored.key()).unwrap();
+        assert!(outbox.push(&stored, &[1]).unwrap());
+    }
+
+    #[test]
+    fn pending_is_oldest_first() {
+        let outbox = temp_outbox("order");
+        let mut newer = entry("task-2", "def");
+        newer.created_at = 200;
+        let mut older = entry("task-1", "abc");
+        older.created_at = 100;
+        outbox.push(&newer, &[2]).unwrap();
+        outbox.push(&older, &[1]).unwrap();
+
+        let keys: Vec<_> = outbox
+            .pending()
+            .unwrap()
+            .iter()
+            .map(OutboxEntry::key)
+            .collect();
+        assert_eq!(keys, vec![older.key(), newer.key()]);
+    }
+
+    #[test]
+    fn entry_key_is_a_safe_file_name() {
+        assert_eq!(entry_key("../etc/passwd", "abc"), "___etc_passwd-abc");
+        assert_eq!(entry_key("task_1", "abc"), "task_1-abc");
+    }
+}
@@ -1,81 +1,654 @@
-use nexus_sdk::{
-    stwo::seq::Stwo,
-    Local,
-    Prover,
-    Viewable,
-};
+use nexus_sdk::{Verifiable, Viewable};
 
 use crate::config;
-use crate::flops;
-use crate::orchestrator_client::OrchestratorClient;
+use crate::memory_stats::get_memory_info;
+use crate::nexus_orchestrator::GetProofTaskResponse;
+use crate::node_capabilities::{self, NodeCapabilities};
+use crate::node_key::NodeKey;
+use crate::orchestrator_client::{OrchestratorClient, OrchestratorError};
+use crate::program_registry::{ProgramRegistry, DEFAULT_PROGRAM_ID};
+use crate::proof_outbox::{self, OutboxEntry, ProofOutbox};
+use crate::proving_error::ProvingError;
+use crate::public_input::PublicInput;
+use crate::session::{self, SessionStats};
+use crate::settings::Settings;
 use crate::setup;
+use crate::task_state::{TaskLifecycle, TaskState};
+use crate::telemetry::{ProofMeter, ProofMetrics};
 use crate::utils;
 use bincode::serialize;
 use colored::Colorize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{mpsc, Mutex};
+use tokio_util::sync::CancellationToken;
 
-/// Proves a program with a given node ID
-#[allow(dead_code)]
-async fn authenticated_proving(
-    node_id: &str,
-    environment: &config::Environment,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let client = OrchestratorClient::new(environment.clone());
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Approximate peak memory of a single Stwo proof, used to size the default
+/// worker pool
+const MEMORY_PER_WORKER_MB: i32 = 2048;
+
+/// Options controlling how the prover runs
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ProverOptions {
+    /// Number of proofs to compute in parallel [default: derived from CPU count and memory]
+    #[arg(long)]
+    pub workers: Option<usize>,
+
+    /// Directory of guest program ELFs, overriding the programs bundled with the CLI [default: ~/.nexus/programs]
+    #[arg(long)]
+    pub programs_dir: Option<PathBuf>,
+}
+
+impl ProverOptions {
+    /// The requested number of workers, or one worker per core bounded by the
+    /// memory available for proving.
+    fn worker_count(&self) -> usize {
+        if let Some(workers) = self.workers {
+            return workers.max(1);
+        }
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let (_, total_memory) = get_memory_info();
+        let by_memory = (total_memory / MEMORY_PER_WORKER_MB).max(1) as usize;
+        cores.min(by_memory)
+    }
+
+    /// The embedded programs, plus any found in the programs directory.
+    fn program_registry(&self) -> Result<ProgramRegistry, Box<dyn std::error::Error>> {
+        let mut registry = ProgramRegistry::default();
+        let dir = match &self.programs_dir {
+            Some(dir) => Some(dir.clone()),
+            // The default directory is optional
+            None => ProgramRegistry::default_dir().filter(|dir| dir.is_dir()),
+        };
+        if let Some(dir) = dir {
+            let count = registry.load_directory(&dir)?;
+            println!(
+                "{}: {} from {}",
+                "Guest programs loaded".bold(),
+                count,
+                dir.display()
+            );
+        }
+        Ok(registry)
+    }
+}
+
+/// State shared by all proving workers
+#[derive(Clone)]
+struct ProverContext {
+    registry: Arc<ProgramRegistry>,
+    session: Arc<SessionStats>,
+    /// Fired on the first shutdown signal: no new proofs are started
+    shutdown: CancellationToken,
+    /// Fired on forced exit: in-flight proofs are abandoned
+    cancel: CancellationToken,
+    /// Verify each proof before handing it to the submit stage
+    verify_proofs: bool,
+    /// Measure peak RSS per proof, which is only meaningful with one worker
+    track_peak_rss: bool,
+}
+
+/// Proof counters for a single proving worker
+#[derive(Default)]
+struct WorkerStats {
+    proved: AtomicUsize,
+    failed: AtomicUsize,
+}
 
-    println!("1. Fetching a task to prove from Nexus Orchestrator...");
-    let proof_task = client.get_proof_task(node_id).await?;
-    println!("2. Received a task to prove from Nexus Orchestrator...");
+/// A task received from the orchestrator, numbered in the order it was fetched
+struct FetchedTask {
+    number: usize,
+    task: GetProofTaskResponse,
+    lifecycle: TaskLifecycle,
+}
 
-    // Fixed: Direct cast instead of parse()
-    let public_input: u32 = proof_task.public_inputs[0] as u32;
+/// A proof waiting to be submitted to the orchestrator
+struct ProvedTask {
+    number: usize,
+    lifecycle: TaskLifecycle,
+    proof_hash: String,
+    proof_bytes: Vec<u8>,
+    metrics: ProofMetrics,
+}
 
-    println!("3. Compiling guest program...");
-    let elf_file_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("assets")
-        .join("fib_input");
-    let prover =
-        Stwo::<Local>::new_from_file(&elf_file_path).expect("failed to load guest program");
+/// A task that could not be proven, waiting to be reported to the orchestrator
+struct FailedTask {
+    number: usize,
+    lifecycle: TaskLifecycle,
+    error: ProvingError,
+}
 
-    println!("4. Creating ZK proof with inputs...");
-    let (view, proof) = prover
-        .prove_with_input::<(), u32>(&(), &public_input)
-        .expect("Failed to run prover");
+/// Result of the prove stage, handed to the submit stage
+enum TaskOutcome {
+    Proved(ProvedTask),
+    Failed(FailedTask),
+}
+
+/// Fetches tasks until shutdown, or until the orchestrator rejects this node.
+/// Blocks on the bounded channel, so at most one task per worker is fetched
+/// ahead of the ones being proven.
+async fn fetch_tasks(
+    node_id: String,
+    client: Arc<OrchestratorClient>,
+    tasks: mpsc::Sender<FetchedTask>,
+    shutdown: CancellationToken,
+) -> Result<(), OrchestratorError> {
+    let mut number = 1;
+    loop {
+        let result = tokio::select! {
+            result = client.get_proof_task(&node_id) => result,
+            _ = shutdown.cancelled() => return Ok(()),
+        };
+        match result {
+            Ok(task) => {
+                println!(
+                    "[task #{}] Received a task to prove from Nexus Orchestrator",
+                    number
+                );
+                let mut lifecycle = TaskLifecycle::fetched(&task.task_id);
+                // Wait for room in the queue without giving up the task, so it
+                // can still be reported if shutdown comes first
+                let permit = tokio::select! {
+                    permit = tasks.reserve() => permit.ok(),
+                    _ = shutdown.cancelled() => None,
+                };
+                let Some(permit) = permit else {
+                    lifecycle.advance(TaskState::Failed);
+                    let failed = FailedTask {
+                        number,
+                        lifecycle,
+                        error: ProvingError::Cancelled,
+                    };
+                    report_failure(&node_id, &client, failed).await;
+                    return Ok(());
+                };
+                permit.send(FetchedTask {
+                    number,
+                    task,
+                    lifecycle,
+                });
+                number += 1;
+            }
+            Err(e) => {
+                println!("Error fetching task: {}", e);
+                if e.is_fatal() {
+                    return Err(e);
+                }
+                let delay = match &e {
+                    // Back off harder when the orchestrator asks us to
+                    OrchestratorError::RateLimited { retry_after, .. } => {
+                        retry_after.unwrap_or(std::time::Duration::from_secs(60))
+                    }
+                    _ => std::time::Duration::from_secs(4),
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = shutdown.cancelled() => return Ok(()),
+                }
+            }
+        }
+    }
+}
 
-    assert_eq!(view.exit_code().expect("failed to retrieve exit code"), 0);
+/// Runs a blocking proving function on tokio's blocking thread pool, keeping
+/// the async runtime free for networking and signal handling.
+///
+/// The Stwo prover cannot be interrupted mid-proof, so `cancel` is checked by
+/// `prove` between steps; if it fires while a step is running, the pending
+/// result is abandoned and the thread finishes in the background.
+async fn prove_blocking<T, F>(cancel: &CancellationToken, prove: F) -> Result<T, ProvingError>
+where
+    T: Send + 'static,
+    F: FnOnce(&CancellationToken) -> Result<T, ProvingError> + Send + 'static,
+{
+    let token = cancel.clone();
+    let handle = tokio::task::spawn_blocking(move || prove(&token));
+    tokio::select! {
+        result = handle => {
+            result.unwrap_or_else(|e| Err(ProvingError::Panicked(e.to_string())))
+        }
+        _ = cancel.cancelled() => Err(ProvingError::Cancelled),
+    }
+}
+
+/// Fails with the guest's exit code and logs unless it exited successfully
+fn check_guest_exit(view: &impl Viewable) -> Result<(), ProvingError> {
+    let exit_code = view
+        .exit_code()
+        .map_err(|e| ProvingError::ExitCode(e.to_string()))?;
+    if exit_code != 0 {
+        return Err(ProvingError::GuestExit {
+            exit_code,
+            logs: view.logs().unwrap_or_default(),
+        });
+    }
+    Ok(())
+}
+
+/// Prints a proving error along with any output from the guest program
+fn print_proving_error(context: &str, e: &ProvingError) {
+    println!("{}: {}", context, e);
+    for line in e.guest_logs() {
+        println!("\t[guest] {}", line);
+    }
+}
+
+/// Proves a task received from the orchestrator and returns the serialized
+/// proof along with the resources used to create it. With `verify`, the proof
+/// is checked against the guest's execution, including its exit code and
+/// public output, before it is returned.
+fn prove_task(
+    proof_task: &GetProofTaskResponse,
+    registry: &ProgramRegistry,
+    cancel: &CancellationToken,
+    verify: bool,
+    track_peak_rss: bool,
+) -> Result<(Vec<u8>, ProofMetrics), ProvingError> {
+    let program_id = if proof_task.program_id.is_empty() {
+        DEFAULT_PROGRAM_ID
+    } else {
+        &proof_task.program_id
+    };
+    let public_input = registry
+        .input_schema(program_id)?
+        .decode(&proof_task.public_inputs)?;
+
+    println!("1. Loading guest program {}...", program_id);
+    let load_started = Instant::now();
+    let prover = registry.load(program_id)?;
+    let load_time = load_started.elapsed();
+
+    if cancel.is_cancelled() {
+        return Err(ProvingError::Cancelled);
+    }
+    println!("2. Creating ZK proof with input {}...", public_input);
+    let meter = ProofMeter::start(track_peak_rss);
+    let (view, proof) = public_input
+        .prove(prover)
+        .map_err(|e| ProvingError::Prover(e.to_string()))?;
+
+    check_guest_exit(&view)?;
 
     let proof_bytes = serialize(&proof)?;
+    let metrics = meter.finish(proof_bytes.len());
 
+    if verify {
+        if cancel.is_cancelled() {
+            return Err(ProvingError::Cancelled);
+        }
+        let verify_started = Instant::now();
+        proof
+            .verify(&view)
+            .map_err(|e| ProvingError::Verification(e.to_string()))?;
+        println!("\tVerified proof in {:.2?}", verify_started.elapsed());
+    }
+
+    println!(
+        "\tLoaded program in {:.2?}, created proof in {:.2?} (CPU time {})",
+        load_time,
+        metrics.wall_time,
+        metrics
+            .cpu_time
+            .map(|t| format!("{:.2?}", t))
+            .unwrap_or_else(|| "unknown".to_string())
+    );
     println!("\tProof size: {} bytes", proof_bytes.len());
-    println!("5. Submitting ZK proof to Nexus Orchestrator...");
-    client
-        .submit_proof(node_id, &proof_hash(proof_bytes.clone()), proof_bytes)
-        .await?;
-    println!("{}", "6. ZK proof successfully submitted".green());
+    Ok((proof_bytes, metrics))
+}
 
-    Ok(())
+/// Proves tasks from the shared queue on the blocking thread pool until the
+/// queue is closed. Tasks still queued at shutdown are not proven but reported
+/// to the orchestrator as cancelled.
+async fn prove_tasks(
+    worker: usize,
+    tasks: Arc<Mutex<mpsc::Receiver<FetchedTask>>>,
+    outcomes: mpsc::Sender<TaskOutcome>,
+    stats: Arc<WorkerStats>,
+    ctx: ProverContext,
+) {
+    let ProverContext {
+        registry,
+        session,
+        shutdown,
+        cancel,
+        verify_proofs,
+        track_peak_rss,
+    } = ctx;
+
+    loop {
+        // Only hold the lock while waiting for the next task
+        let next = tasks.lock().await.recv().await;
+        let Some(FetchedTask {
+            number,
+            task,
+            mut lifecycle,
+        }) = next
+        else {
+            return;
+        };
+        if shutdown.is_cancelled() {
+            // Hand the task back to the orchestrator rather than letting it
+            // time out there
+            println!("[task #{}] Shutting down, task will not be proven", number);
+            lifecycle.advance(TaskState::Failed);
+            let failed = FailedTask {
+                number,
+                lifecycle,
+                error: ProvingError::Cancelled,
+            };
+            if outcomes.send(TaskOutcome::Failed(failed)).await.is_err() {
+                return;
+            }
+            continue;
+        }
+        session.attempted.fetch_add(1, Ordering::Relaxed);
+
+        let attempt =
+            stats.proved.load(Ordering::Relaxed) + stats.failed.load(Ordering::Relaxed) + 1;
+        println!("\n================================================");
+        println!(
+            "{}",
+            format!(
+                "\n[worker {}] Starting proof #{} (task #{}) ...\n",
+                worker, attempt, number
+            )
+            .yellow()
+        );
+
+        lifecycle.advance(TaskState::Proving);
+        let registry = registry.clone();
+        let result = prove_blocking(&cancel, move |cancel| {
+            prove_task(&task, &registry, cancel, verify_proofs, track_peak_rss)
+        })
+        .await;
+        let outcome = match result {
+            Ok((proof_bytes, metrics)) => {
+                stats.proved.fetch_add(1, Ordering::Relaxed);
+                session.proved.fetch_add(1, Ordering::Relaxed);
+                session
+                    .proof_bytes
+                    .fetch_add(proof_bytes.len() as u64, Ordering::Relaxed);
+                lifecycle.advance(TaskState::Proved);
+                TaskOutcome::Proved(ProvedTask {
+                    number,
+                    lifecycle,
+                    proof_hash: proof_hash(&proof_bytes),
+                    proof_bytes,
+                    metrics,
+                })
+            }
+            Err(error) => {
+                stats.failed.fetch_add(1, Ordering::Relaxed);
+                session.failed.fetch_add(1, Ordering::Relaxed);
+                print_proving_error(
+                    &format!("[worker {}] Error in authenticated proving", worker),
+                    &error,
+                );
+                lifecycle.advance(TaskState::Failed);
+                TaskOutcome::Failed(FailedTask {
+                    number,
+                    lifecycle,
+                    error,
+                })
+            }
+        };
+        if outcomes.send(outcome).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Submits a proof, keeping it in the outbox unless the orchestrator rejects it.
+/// Returns `false` if the same submission is already queued in the outbox,
+/// which then sends it instead.
+async fn submit_proof(
+    node_id: &str,
+    client: &OrchestratorClient,
+    outbox: Option<&ProofOutbox>,
+    proved: ProvedTask,
+) -> Result<bool, BoxError> {
+    let ProvedTask {
+        number,
+        mut lifecycle,
+        proof_hash,
+        proof_bytes,
+        metrics,
+    } = proved;
+
+    let outbox_key = proof_outbox::entry_key(&lifecycle.task_id, &proof_hash);
+
+    // Persist the proof first so it is not lost if submission fails
+    if let Some(outbox) = outbox {
+        let entry = OutboxEntry::new(node_id, lifecycle.clone(), &proof_hash);
+        match outbox.push(&entry, &proof_bytes) {
+            Ok(true) => {}
+            Ok(false) => {
+                println!(
+                    "[proof #{}] Submission already queued in outbox, leaving it to the outbox",
+                    number
+                );
+                return Ok(false);
+            }
+            Err(e) => println!("\tFailed to store proof in outbox: {}", e),
+        }
+    }
+
+    println!(
+        "[proof #{}] Submitting ZK proof to Nexus Orchestrator...",
+        number
+    );
+    lifecycle.advance(TaskState::Submitting);
+    match client
+        .submit_proof(
+            node_id,
+            &lifecycle.task_id,
+            &proof_hash,
+            proof_bytes,
+            Some(&metrics),
+        )
+        .await
+    {
+        Ok(()) => {
+            lifecycle.advance(TaskState::Submitted);
+            if let Some(outbox) = outbox {
+                outbox.remove(&outbox_key)?;
+            }
+        }
+        Err(e) => {
+            // Only a definitive rejection discards the proof; transient and
+            // fatal errors (e.g. a wrong API key) leave it for a later run
+            let keep = outbox.is_some() && !e.is_rejection();
+            if keep {
+                lifecycle.advance(TaskState::Proved);
+            } else {
+                lifecycle.advance(TaskState::Failed);
+            }
+            if let Some(outbox) = outbox {
+                if keep {
+                    println!("\tProof kept in outbox for later submission");
+                    outbox.release(&outbox_key);
+                } else {
+                    outbox.remove(&outbox_key)?;
+                }
+            }
+            return Err(e.into());
+        }
+    }
+    println!(
+        "{}",
+        format!("[proof #{}] ZK proof successfully submitted", number).green()
+    );
+
+    Ok(true)
+}
+
+/// Reports a task that could not be proven so the orchestrator can reassign it
+async fn report_failure(node_id: &str, client: &OrchestratorClient, failed: FailedTask) {
+    let FailedTask {
+        number,
+        lifecycle,
+        error,
+    } = failed;
+
+    println!(
+        "[task #{}] Reporting failed task to Nexus Orchestrator...",
+        number
+    );
+    if let Err(e) = client
+        .report_task_failure(
+            node_id,
+            &lifecycle.task_id,
+            error.category(),
+            error.exit_code(),
+            &error.to_string(),
+        )
+        .await
+    {
+        println!("Error reporting task failure: {}", e);
+    }
+}
+
+async fn submit_proofs(
+    node_id: String,
+    client: Arc<OrchestratorClient>,
+    outbox: Option<Arc<ProofOutbox>>,
+    mut outcomes: mpsc::Receiver<TaskOutcome>,
+    session: Arc<SessionStats>,
+) {
+    while let Some(outcome) = outcomes.recv().await {
+        let proved = match outcome {
+            TaskOutcome::Proved(proved) => proved,
+            TaskOutcome::Failed(failed) => {
+                report_failure(&node_id, &client, failed).await;
+                continue;
+            }
+        };
+        match submit_proof(&node_id, &client, outbox.as_deref(), proved).await {
+            Ok(true) => {
+                session.submitted.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                session.submit_failed.fetch_add(1, Ordering::Relaxed);
+                println!("Error submitting proof: {}", e);
+            }
+        }
+    }
 }
 
-// Helper function for proof hashing
-fn proof_hash(proof_bytes: Vec<u8>) -> String {
+/// Runs fetching, proving and submission as concurrent stages connected by
+/// bounded channels, so the next tasks are fetched and previous proofs are
+/// submitted while `workers` proofs are being computed.
+///
+/// Returns once `shutdown` fires (or the orchestrator rejects this node) and
+/// all in-flight proofs have been submitted or stored in the outbox.
+async fn run_pipeline(
+    node_id: String,
+    client: Arc<OrchestratorClient>,
+    outbox: Option<Arc<ProofOutbox>>,
+    workers: usize,
+    ctx: ProverContext,
+) -> Result<(), OrchestratorError> {
+    let (task_tx, task_rx) = mpsc::channel(workers);
+    let (outcome_tx, outcome_rx) = mpsc::channel(workers);
+    let task_rx = Arc::new(Mutex::new(task_rx));
+
+    let fetcher = tokio::spawn(fetch_tasks(
+        node_id.clone(),
+        client.clone(),
+        task_tx,
+        ctx.shutdown.clone(),
+    ));
+    let stats: Vec<Arc<WorkerStats>> = (0..workers).map(|_| Arc::default()).collect();
+    let provers: Vec<_> = stats
+        .iter()
+        .enumerate()
+        .map(|(worker, stats)| {
+            tokio::spawn(prove_tasks(
+                worker + 1,
+                task_rx.clone(),
+                outcome_tx.clone(),
+                stats.clone(),
+                ctx.clone(),
+            ))
+        })
+        .collect();
+    drop(outcome_tx);
+    let submitter = tokio::spawn(submit_proofs(
+        node_id,
+        client.clone(),
+        outbox.clone(),
+        outcome_rx,
+        ctx.session.clone(),
+    ));
+
+    // The fetcher stops on shutdown or a fatal error; the later stages then
+    // finish the work already queued and stop once their input channel is closed
+    let result = fetcher.await.expect("task fetcher panicked");
+    for prover in provers {
+        prover.await.expect("prover panicked");
+    }
+    submitter.await.expect("proof submitter panicked");
+
+    // Give proofs left over from failed submissions one last chance
+    if let Some(outbox) = &outbox {
+        let submitted = outbox.drain(&client).await;
+        if submitted > 0 {
+            println!("Flushed {} pending proof(s) from the outbox", submitted);
+        }
+    }
+
+    for (worker, stats) in stats.iter().enumerate() {
+        println!(
+            "[worker {}] {} proofs created, {} failed",
+            worker + 1,
+            stats.proved.load(Ordering::Relaxed),
+            stats.failed.load(Ordering::Relaxed)
+        );
+    }
+    result
+}
+
+/// Keccak-256 of a serialized proof, as submitted to the orchestrator
+pub(crate) fn proof_hash(proof_bytes: &[u8]) -> String {
     use sha3::{Digest, Keccak256};
-    format!("{:x}", Keccak256::digest(&proof_bytes))
+    format!("{:x}", Keccak256::digest(proof_bytes))
 }
 
-fn anonymous_proving() -> Result<(), Box<dyn std::error::Error>> {
-    let public_input: u32 = 9;
+/// Proves the default program with a fixed input and returns the proof size
+fn anonymous_proving(
+    registry: &ProgramRegistry,
+    cancel: &CancellationToken,
+) -> Result<usize, ProvingError> {
+    let public_input = PublicInput::U32(9);
 
-    println!("1. Compiling guest program...");
-    let elf_file_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("assets")
-        .join("fib_input");
-    let prover =
-        Stwo::<Local>::new_from_file(&elf_file_path).expect("failed to load guest program");
+    println!("1. Loading guest program {}...", DEFAULT_PROGRAM_ID);
+    let load_started = Instant::now();
+    let prover = registry.load(DEFAULT_PROGRAM_ID)?;
+    let load_time = load_started.elapsed();
 
+    if cancel.is_cancelled() {
+        return Err(ProvingError::Cancelled);
+    }
     println!("2. Creating ZK proof...");
-    let (view, proof) = prover
-        .prove_with_input::<(), u32>(&(), &public_input)
-        .expect("Failed to run prover");
+    let prove_started = Instant::now();
+    let (view, proof) = public_input
+        .prove(prover)
+        .map_err(|e| ProvingError::Prover(e.to_string()))?;
+    println!(
+        "\tLoaded program in {:.2?}, created proof in {:.2?}",
+        load_time,
+        prove_started.elapsed()
+    );
 
-    assert_eq!(view.exit_code().expect("failed to retrieve exit code"), 0);
+    check_guest_exit(&view)?;
 
     let proof_bytes = serialize(&proof)?;
 
@@ -87,13 +660,17 @@ fn anonymous_proving() -> Result<(), Box<dyn std::error::Error>> {
         )
         .green(),
     );
-    Ok(())
+    Ok(proof_bytes.len())
 }
 
-/// Starts the prover, which can be anonymous or connected to the Nexus Orchestrator
+/// Starts the prover, which can be anonymous or connected to the Nexus Orchestrator.
+///
+/// Runs until interrupted by SIGINT/SIGTERM, then returns an exit code
+/// reflecting whether all proofs of the session succeeded.
 pub async fn start_prover(
     environment: &config::Environment,
-) -> Result<(), Box<dyn std::error::Error>> {
+    options: &ProverOptions,
+) -> Result<ExitCode, Box<dyn std::error::Error>> {
     // Print the banner at startup
     utils::cli_branding::print_banner();
 
@@ -105,8 +682,19 @@ pub async fn start_prover(
             .bright_cyan(),
     );
 
+    let settings = Settings::load()?;
+
     // Run the initial setup to determine anonymous or connected node
-    match setup::run_initial_setup().await {
+    let setup_result = setup::run_initial_setup().await;
+
+    // Installed after setup so Ctrl-C still aborts the interactive prompts
+    let shutdown = CancellationToken::new();
+    let cancel = CancellationToken::new();
+    session::spawn_signal_handler(shutdown.clone(), cancel.clone());
+    let session = Arc::new(SessionStats::default());
+    let registry = Arc::new(options.program_registry()?);
+
+    match setup_result {
         setup::SetupResult::Anonymous => {
             println!(
                 "\n===== {} =====\n",
@@ -117,19 +705,36 @@ pub async fn start_prover(
             );
             // Run the proof generation loop with anonymous proving
             let mut proof_count = 1;
-            loop {
+            while !shutdown.is_cancelled() {
                 println!("\n================================================");
                 println!(
                     "{}",
                     format!("\nStarting proof #{} ...\n", proof_count).yellow()
                 );
-                match anonymous_proving() {
-                    Ok(_) => (),
-                    Err(e) => println!("Error in anonymous proving: {}", e),
+                session.attempted.fetch_add(1, Ordering::Relaxed);
+                let registry = registry.clone();
+                match prove_blocking(&cancel, move |cancel| anonymous_proving(&registry, cancel))
+                    .await
+                {
+                    Ok(size) => {
+                        session.proved.fetch_add(1, Ordering::Relaxed);
+                        session
+                            .proof_bytes
+                            .fetch_add(size as u64, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        session.failed.fetch_add(1, Ordering::Relaxed);
+                        print_proving_error("Error in anonymous proving", &e);
+                    }
                 }
                 proof_count += 1;
-                tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(4)) => {}
+                    _ = shutdown.cancelled() => {}
+                }
             }
+            session.print_summary();
+            Ok(session.exit_code())
         }
         setup::SetupResult::Connected(node_id) => {
             println!(
@@ -139,8 +744,13 @@ pub async fn start_prover(
                     .underline()
                     .bright_cyan()
             );
-            let flops = flops::measure_flops();
-            let flops_formatted = format!("{:.2}", flops);
+            let refresh_interval = settings.capabilities_refresh_interval();
+            // Measuring runs a CPU benchmark, which must not stall the runtime
+            let capabilities = tokio::task::spawn_blocking(move || {
+                NodeCapabilities::load_or_measure(refresh_interval)
+            })
+            .await?;
+            let flops_formatted = format!("{:.2}", capabilities.flops_per_sec);
             let flops_str = format!("{} FLOPS", flops_formatted);
             println!(
                 "{}: {}",
@@ -157,23 +767,79 @@ pub async fn start_prover(
                 "Environment".bold(),
                 environment.to_string().bright_cyan()
             );
+            let workers = options.worker_count();
+            println!(
+                "{}: {}",
+                "Proving workers".bold(),
+                workers.to_string().bright_cyan()
+            );
 
-            let mut proof_count = 1;
-            loop {
-                println!("\n================================================");
-                println!(
-                    "{}",
-                    format!("\nStarting proof #{} ...\n", proof_count).yellow()
-                );
+            let location = settings.resolve_location()?;
+            println!(
+                "{}: {}",
+                "Location".bold(),
+                location.as_deref().unwrap_or("not reported").bright_cyan()
+            );
 
-                match authenticated_proving(&node_id, environment).await {
-                    Ok(_) => (),
-                    Err(e) => println!("Error in authenticated proving: {}", e),
-                }
+            let capabilities = Arc::new(std::sync::RwLock::new(capabilities));
+            node_capabilities::spawn_refresher(capabilities.clone(), refresh_interval);
 
-                proof_count += 1;
-                tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+            let mut client = OrchestratorClient::new_with_config(
+                environment.clone(),
+                &settings.http,
+                Some(node_id.as_str()),
+            )?
+            .with_location(location)
+            .with_capabilities(capabilities)
+            .with_api_key(settings.api_key.clone())
+            .with_proof_codec(settings.proof_compression);
+            // The key pair is created on first run and attests every proof
+            match NodeKey::load_or_generate() {
+                Ok(node_key) => {
+                    println!(
+                        "{}: {}",
+                        "Node public key".bold(),
+                        node_key.public_key_hex().bright_cyan()
+                    );
+                    client = client
+                        .with_node_key(Arc::new(node_key))
+                        .with_request_signing(settings.sign_requests);
+                }
+                Err(e) if settings.sign_requests => return Err(e.into()),
+                Err(e) => println!("Proof attestation disabled: {}", e),
+            }
+            let client = Arc::new(client);
+            let outbox = match ProofOutbox::default_dir().map(ProofOutbox::open) {
+                Some(Ok(outbox)) => Some(Arc::new(outbox)),
+                Some(Err(e)) => {
+                    println!("Proof outbox disabled: {}", e);
+                    None
+                }
+                None => {
+                    println!("Proof outbox disabled: home directory not found");
+                    None
+                }
+            };
+            if let Some(outbox) = &outbox {
+                proof_outbox::spawn_drainer(
+                    outbox.clone(),
+                    client.clone(),
+                    std::time::Duration::from_secs(60),
+                );
             }
+
+            let ctx = ProverContext {
+                registry,
+                session: session.clone(),
+                shutdown,
+                cancel,
+                verify_proofs: settings.verify_proofs,
+                track_peak_rss: workers == 1,
+            };
+            let result = run_pipeline(node_id, client, outbox, workers, ctx).await;
+            session.print_summary();
+            result?;
+            Ok(session.exit_code())
         }
         setup::SetupResult::Invalid => {
             return Err("Invalid setup option selected".into());
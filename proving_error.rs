@@ -0,0 +1,76 @@
+use crate::nexus_orchestrator::TaskFailureCategory;
+use crate::program_registry::ProgramError;
+use crate::public_input::InputError;
+use thiserror::Error;
+
+/// Reasons a single proof could not be created. None of these stop the prover;
+/// the task is counted as failed and the next one is started.
+#[derive(Debug, Error)]
+pub enum ProvingError {
+    #[error("invalid task input: {0}")]
+    InvalidInput(#[from] InputError),
+
+    #[error("failed to load guest program: {0}")]
+    Load(#[from] ProgramError),
+
+    #[error("failed to run prover: {0}")]
+    Prover(String),
+
+    #[error("guest program exited with code {exit_code}")]
+    GuestExit {
+        exit_code: u32,
+        /// Output logged by the guest before exiting
+        logs: Vec<String>,
+    },
+
+    #[error("failed to read guest exit code: {0}")]
+    ExitCode(String),
+
+    #[error("proof failed local verification: {0}")]
+    Verification(String),
+
+    #[error("failed to serialize proof: {0}")]
+    Serialize(#[from] bincode::Error),
+
+    #[error("prover thread panicked: {0}")]
+    Panicked(String),
+
+    /// The task was fetched but abandoned at shutdown. Reported so that the
+    /// orchestrator can reassign it; a forced exit also cancels in-flight
+    /// proofs, but the process exits before they can be reported.
+    #[error("proving cancelled")]
+    Cancelled,
+}
+
+impl ProvingError {
+    /// How the failure is reported to the orchestrator.
+    pub fn category(&self) -> TaskFailureCategory {
+        match self {
+            Self::InvalidInput(_) => TaskFailureCategory::MalformedTask,
+            Self::Load(_) => TaskFailureCategory::ProgramUnavailable,
+            Self::GuestExit { .. } => TaskFailureCategory::GuestExit,
+            Self::Cancelled => TaskFailureCategory::Cancelled,
+            Self::Prover(_)
+            | Self::ExitCode(_)
+            | Self::Verification(_)
+            | Self::Serialize(_)
+            | Self::Panicked(_) => TaskFailureCategory::ProverError,
+        }
+    }
+
+    /// The guest's exit code, if it ran to completion.
+    pub fn exit_code(&self) -> Option<u32> {
+        match self {
+            Self::GuestExit { exit_code, .. } => Some(*exit_code),
+            _ => None,
+        }
+    }
+
+    /// Guest output to show alongside the error, if any.
+    pub fn guest_logs(&self) -> &[String] {
+        match self {
+            Self::GuestExit { logs, .. } => logs,
+            _ => &[],
+        }
+    }
+}
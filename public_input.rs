@@ -0,0 +1,205 @@
+use nexus_sdk::{stwo::seq::Stwo, Local, Prover};
+use thiserror::Error;
+
+type StwoProver = Stwo<Local>;
+/// Proof produced by [`PublicInput::prove`], serialized with bincode
+pub type StwoProof = <StwoProver as Prover>::Proof;
+/// Execution trace of a guest program, holding its exit code and public output
+pub type StwoView = <StwoProver as Prover>::View;
+
+/// Result of proving a guest program with [`PublicInput::prove`]
+pub type ProveResult = Result<(StwoView, StwoProof), <StwoProver as Prover>::Error>;
+
+/// How a program expects its public input to be encoded in the
+/// `public_inputs` bytes of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum InputSchema {
+    /// A single little-endian `u32`. A single byte is also accepted, as sent
+    /// for small values by older orchestrator versions.
+    U32,
+    /// Two little-endian `u32` values, passed to the guest as a tuple
+    U32Pair,
+    /// Three little-endian `u32` values, passed to the guest as a tuple
+    U32Triple,
+    /// Structured input already encoded by the orchestrator, passed through as bytes
+    Bytes,
+}
+
+/// A decoded public input, ready to be passed to the guest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicInput {
+    U32(u32),
+    U32Pair(u32, u32),
+    U32Triple(u32, u32, u32),
+    Bytes(Vec<u8>),
+}
+
+/// A task whose public inputs do not match what its program expects
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("task has no public inputs")]
+    Empty,
+
+    #[error("expected {expected} bytes of public input for {schema:?}, got {actual}")]
+    InvalidLength {
+        schema: InputSchema,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl InputSchema {
+    /// Decodes and validates the public inputs of a task.
+    pub fn decode(&self, bytes: &[u8]) -> Result<PublicInput, InputError> {
+        if bytes.is_empty() {
+            return Err(InputError::Empty);
+        }
+
+        let words = |count: usize| -> Result<Vec<u32>, InputError> {
+            if bytes.len() != count * 4 {
+                return Err(InputError::InvalidLength {
+                    schema: *self,
+                    expected: count * 4,
+                    actual: bytes.len(),
+                });
+            }
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect())
+        };
+
+        match self {
+            Self::U32 if bytes.len() == 1 => Ok(PublicInput::U32(bytes[0] as u32)),
+            Self::U32 => Ok(PublicInput::U32(words(1)?[0])),
+            Self::U32Pair => {
+                let words = words(2)?;
+                Ok(PublicInput::U32Pair(words[0], words[1]))
+            }
+            Self::U32Triple => {
+                let words = words(3)?;
+                Ok(PublicInput::U32Triple(words[0], words[1], words[2]))
+            }
+            Self::Bytes => Ok(PublicInput::Bytes(bytes.to_vec())),
+        }
+    }
+}
+
+impl PublicInput {
+    /// Proves the program loaded in `prover` with this value as its public input.
+    pub fn prove(&self, prover: StwoProver) -> ProveResult {
+        match self {
+            Self::U32(value) => prover.prove_with_input::<(), u32>(&(), value),
+            Self::U32Pair(a, b) => prover.prove_with_input::<(), (u32, u32)>(&(), &(*a, *b)),
+            Self::U32Triple(a, b, c) => {
+                prover.prove_with_input::<(), (u32, u32, u32)>(&(), &(*a, *b, *c))
+            }
+            Self::Bytes(bytes) => prover.prove_with_input::<(), Vec<u8>>(&(), bytes),
+        }
+    }
+
+    /// Runs the program loaded in `prover` with this value as its public input
+    /// without proving it. The view holds the exit code and public output a
+    /// proof of the same run must match.
+    pub fn run(&self, prover: &StwoProver) -> Result<StwoView, <StwoProver as Prover>::Error> {
+        match self {
+            Self::U32(value) => prover.run_with_input::<(), u32>(&(), value),
+            Self::U32Pair(a, b) => prover.run_with_input::<(), (u32, u32)>(&(), &(*a, *b)),
+            Self::U32Triple(a, b, c) => {
+                prover.run_with_input::<(), (u32, u32, u32)>(&(), &(*a, *b, *c))
+            }
+            Self::Bytes(bytes) => prover.run_with_input::<(), Vec<u8>>(&(), bytes),
+        }
+    }
+}
+
+impl std::fmt::Display for PublicInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::U32(value) => write!(f, "{}", value),
+            Self::U32Pair(a, b) => write!(f, "({}, {})", a, b),
+            Self::U32Triple(a, b, c) => write!(f, "({}, {}, {})", a, b, c),
+            Self::Bytes(bytes) => write!(f, "{} bytes", bytes.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_rejects_empty_input() {
+        for schema in [
+            InputSchema::U32,
+            InputSchema::U32Pair,
+            InputSchema::U32Triple,
+            InputSchema::Bytes,
+        ] {
+            assert!(matches!(schema.decode(&[]), Err(InputError::Empty)));
+        }
+    }
+
+    #[test]
+    fn decode_u32_accepts_legacy_single_byte() {
+        assert_eq!(InputSchema::U32.decode(&[9]).unwrap(), PublicInput::U32(9));
+        assert_eq!(
+            InputSchema::U32.decode(&[255]).unwrap(),
+            PublicInput::U32(255)
+        );
+    }
+
+    #[test]
+    fn decode_u32_is_little_endian() {
+        assert_eq!(
+            InputSchema::U32.decode(&[1, 2, 0, 0]).unwrap(),
+            PublicInput::U32(0x0201)
+        );
+    }
+
+    #[test]
+    fn decode_tuples() {
+        let pair = [1, 0, 0, 0, 2, 0, 0, 0];
+        assert_eq!(
+            InputSchema::U32Pair.decode(&pair).unwrap(),
+            PublicInput::U32Pair(1, 2)
+        );
+        let triple = [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0];
+        assert_eq!(
+            InputSchema::U32Triple.decode(&triple).unwrap(),
+            PublicInput::U32Triple(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn decode_rejects_wrong_lengths() {
+        for (schema, bytes, expected) in [
+            (InputSchema::U32, &[1, 2][..], 4),
+            (InputSchema::U32, &[1, 2, 3, 4, 5][..], 4),
+            (InputSchema::U32Pair, &[1, 2, 3, 4][..], 8),
+            // The single-byte form is only accepted for a single u32
+            (InputSchema::U32Pair, &[1][..], 8),
+            (InputSchema::U32Triple, &[0; 8][..], 12),
+        ] {
+            match schema.decode(bytes) {
+                Err(InputError::InvalidLength {
+                    expected: e,
+                    actual,
+                    ..
+                }) => {
+                    assert_eq!(e, expected);
+                    assert_eq!(actual, bytes.len());
+                }
+                other => panic!("{:?} decoded {:?} as {:?}", schema, bytes, other),
+            }
+        }
+    }
+
+    #[test]
+    fn decode_bytes_passes_input_through() {
+        assert_eq!(
+            InputSchema::Bytes.decode(&[1, 2, 3]).unwrap(),
+            PublicInput::Bytes(vec![1, 2, 3])
+        );
+    }
+}
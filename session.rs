@@ -0,0 +1,114 @@
+use colored::Colorize;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use tokio_util::sync::CancellationToken;
+
+/// Exit status used when the user forces an exit with a second signal
+const FORCED_EXIT_CODE: i32 = 130;
+
+/// Counters shared by all stages of a prover session
+#[derive(Debug, Default)]
+pub struct SessionStats {
+    pub attempted: AtomicUsize,
+    pub proved: AtomicUsize,
+    pub failed: AtomicUsize,
+    pub submitted: AtomicUsize,
+    pub submit_failed: AtomicUsize,
+    /// Total size of all proofs created, in bytes
+    pub proof_bytes: AtomicU64,
+}
+
+impl SessionStats {
+    pub fn print_summary(&self) {
+        println!(
+            "\n===== {} =====\n",
+            "Session summary".bold().underline().bright_cyan()
+        );
+        println!(
+            "{}: {}",
+            "Proofs attempted".bold(),
+            self.attempted.load(Ordering::Relaxed)
+        );
+        println!(
+            "{}: {}",
+            "Proofs succeeded".bold(),
+            self.proved.load(Ordering::Relaxed).to_string().green()
+        );
+        println!(
+            "{}: {}",
+            "Proofs failed".bold(),
+            self.failed.load(Ordering::Relaxed).to_string().red()
+        );
+        println!(
+            "{}: {}",
+            "Proofs submitted".bold(),
+            self.submitted.load(Ordering::Relaxed)
+        );
+        println!(
+            "{}: {}",
+            "Submissions failed".bold(),
+            self.submit_failed.load(Ordering::Relaxed)
+        );
+        println!(
+            "{}: {} bytes",
+            "Total proof size".bold(),
+            self.proof_bytes.load(Ordering::Relaxed)
+        );
+    }
+
+    /// Success if every proof attempted during the session was created and,
+    /// when connected to the orchestrator, submitted.
+    pub fn exit_code(&self) -> ExitCode {
+        if self.failed.load(Ordering::Relaxed) == 0
+            && self.submit_failed.load(Ordering::Relaxed) == 0
+        {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Waits for SIGINT (Ctrl-C) or, on Unix, SIGTERM
+async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// Installs the shutdown handler: the first signal fires `shutdown` so no new
+/// work is started, the second abandons in-flight proofs via `cancel` and
+/// exits immediately.
+pub fn spawn_signal_handler(shutdown: CancellationToken, cancel: CancellationToken) {
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        println!(
+            "\n{}",
+            "Shutting down after in-flight proofs complete. Press Ctrl-C again to exit immediately."
+                .yellow()
+        );
+        shutdown.cancel();
+
+        wait_for_signal().await;
+        println!("\n{}", "Exiting immediately.".red());
+        cancel.cancel();
+        std::process::exit(FORCED_EXIT_CODE);
+    });
+}
@@ -0,0 +1,139 @@
+use crate::location::{self, InvalidLocation};
+use crate::orchestrator_client::ClientConfig;
+use crate::proof_codec::ProofCodec;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error(transparent)]
+    InvalidLocation(#[from] InvalidLocation),
+
+    #[error("invalid NEXUS_PROOF_COMPRESSION: {0}")]
+    InvalidProofCompression(String),
+}
+
+/// Node settings read from `~/.nexus/config.json`. Every field is optional
+/// and can be overridden with the environment variable named in its docs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// ISO 3166 country or region code reported in telemetry, e.g. "DE" or
+    /// "US-CA" (`NEXUS_LOCATION`)
+    pub location: Option<String>,
+
+    /// Guess the location from the system locale and timezone when `location`
+    /// is not set (`NEXUS_DETECT_LOCATION`)
+    pub detect_location: bool,
+
+    /// How often to re-run the FLOPS benchmark, in seconds; defaults to once
+    /// a day (`NEXUS_CAPABILITIES_REFRESH_SECS`)
+    pub capabilities_refresh_secs: Option<u64>,
+
+    /// Verify each proof locally before submitting it, so that a bad proof
+    /// is reported as a failed task instead (`NEXUS_VERIFY_PROOFS`)
+    pub verify_proofs: bool,
+
+    /// Compress proofs before uploading them: "none", "zstd" or "gzip"
+    /// (`NEXUS_PROOF_COMPRESSION`)
+    pub proof_compression: ProofCodec,
+
+    /// API key sent as a bearer token to the orchestrator (`NEXUS_API_KEY`)
+    pub api_key: Option<String>,
+
+    /// Sign every orchestrator request with the node key in
+    /// `~/.nexus/node_key`, generating it if needed (`NEXUS_SIGN_REQUESTS`)
+    pub sign_requests: bool,
+
+    /// Timeouts, proxy and TLS settings for talking to the orchestrator
+    pub http: ClientConfig,
+}
+
+impl Settings {
+    /// The settings file location, `~/.nexus/config.json`.
+    pub fn path() -> Option<PathBuf> {
+        home::home_dir().map(|home| home.join(".nexus").join("config.json"))
+    }
+
+    /// Loads the settings file, if any, and applies environment overrides.
+    pub fn load() -> Result<Self, SettingsError> {
+        let mut settings = match Self::path() {
+            Some(path) if path.exists() => {
+                let contents =
+                    std::fs::read_to_string(&path).map_err(|source| SettingsError::Io {
+                        path: path.clone(),
+                        source,
+                    })?;
+                serde_json::from_str(&contents)
+                    .map_err(|source| SettingsError::Parse { path, source })?
+            }
+            _ => Self::default(),
+        };
+        settings.apply_env()?;
+        Ok(settings)
+    }
+
+    fn apply_env(&mut self) -> Result<(), SettingsError> {
+        if let Ok(location) = std::env::var("NEXUS_LOCATION") {
+            self.location = Some(location);
+        }
+        if let Ok(detect) = std::env::var("NEXUS_DETECT_LOCATION") {
+            self.detect_location = matches!(detect.as_str(), "1" | "true" | "yes");
+        }
+        if let Some(secs) = std::env::var("NEXUS_CAPABILITIES_REFRESH_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+        {
+            self.capabilities_refresh_secs = Some(secs);
+        }
+        if let Ok(verify) = std::env::var("NEXUS_VERIFY_PROOFS") {
+            self.verify_proofs = matches!(verify.as_str(), "1" | "true" | "yes");
+        }
+        if let Ok(codec) = std::env::var("NEXUS_PROOF_COMPRESSION") {
+            self.proof_compression = codec
+                .parse()
+                .map_err(SettingsError::InvalidProofCompression)?;
+        }
+        if let Ok(api_key) = std::env::var("NEXUS_API_KEY") {
+            self.api_key = Some(api_key);
+        }
+        if let Ok(sign) = std::env::var("NEXUS_SIGN_REQUESTS") {
+            self.sign_requests = matches!(sign.as_str(), "1" | "true" | "yes");
+        }
+        self.http.apply_env();
+        Ok(())
+    }
+
+    pub fn capabilities_refresh_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.capabilities_refresh_secs
+                .unwrap_or(24 * 60 * 60)
+                .max(60),
+        )
+    }
+
+    /// The validated location to report, either configured or detected.
+    pub fn resolve_location(&self) -> Result<Option<String>, SettingsError> {
+        match &self.location {
+            Some(location) => Ok(Some(location::validate_location(location)?)),
+            None if self.detect_location => Ok(location::detect_location()),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where a task is in its local lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskState {
+    Fetched,
+    Proving,
+    Proved,
+    Submitting,
+    Submitted,
+    Failed,
+}
+
+impl TaskState {
+    /// Whether the task can move from this state to `next`. A proof whose
+    /// submission failed transiently goes back to `Proved` until it is retried.
+    pub fn can_advance_to(self, next: TaskState) -> bool {
+        use TaskState::*;
+        matches!(
+            (self, next),
+            (Fetched, Proving)
+                | (Proving, Proved)
+                | (Proved, Submitting)
+                | (Submitting, Submitted)
+                | (Submitting, Proved)
+                | (Fetched | Proving | Proved | Submitting, Failed)
+        )
+    }
+}
+
+impl fmt::Display for TaskState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Milliseconds since the Unix epoch
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// The states a task has gone through, with the time each was entered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskLifecycle {
+    pub task_id: String,
+    /// State transitions in order, as (state, milliseconds since the Unix epoch)
+    pub transitions: Vec<(TaskState, u64)>,
+}
+
+impl TaskLifecycle {
+    /// Starts tracking a task that was just received from the orchestrator.
+    pub fn fetched(task_id: &str) -> Self {
+        let lifecycle = Self {
+            task_id: task_id.to_string(),
+            transitions: vec![(TaskState::Fetched, now_millis())],
+        };
+        lifecycle.log();
+        lifecycle
+    }
+
+    pub fn state(&self) -> TaskState {
+        self.transitions
+            .last()
+            .map(|(state, _)| *state)
+            .unwrap_or(TaskState::Fetched)
+    }
+
+    /// Milliseconds since the task was fetched.
+    pub fn age_millis(&self) -> u64 {
+        let fetched_at = self
+            .transitions
+            .first()
+            .map(|(_, at)| *at)
+            .unwrap_or_default();
+        now_millis().saturating_sub(fetched_at)
+    }
+
+    /// Records a transition to `next` and logs it.
+    pub fn advance(&mut self, next: TaskState) {
+        debug_assert!(
+            self.state().can_advance_to(next),
+            "invalid task transition {} -> {}",
+            self.state(),
+            next
+        );
+        self.transitions.push((next, now_millis()));
+        self.log();
+    }
+
+    fn log(&self) {
+        println!(
+            "\t[task {}] {} (+{:.1}s)",
+            self.task_id,
+            self.state(),
+            self.age_millis() as f64 / 1000.0
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::memory_stats::get_memory_info;
+use crate::nexus_orchestrator::NodeTelemetry;
+use crate::node_capabilities::NodeCapabilities;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Version of this CLI, reported with every submission
+pub const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Static description of the CPU, read once
+#[derive(Debug, Clone)]
+pub struct CpuInfo {
+    pub model: Option<String>,
+    pub cores: usize,
+}
+
+impl CpuInfo {
+    pub fn get() -> &'static CpuInfo {
+        static CPU_INFO: OnceLock<CpuInfo> = OnceLock::new();
+        CPU_INFO.get_or_init(|| CpuInfo {
+            model: cpu_model(),
+            cores: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+        })
+    }
+}
+
+/// Resource usage of a single proof
+#[derive(Debug, Clone, Default)]
+pub struct ProofMetrics {
+    pub wall_time: Duration,
+    /// CPU time of the proving thread
+    pub cpu_time: Option<Duration>,
+    /// Peak resident set size of the process while proving, in kB. Only
+    /// measured when a single worker is proving, since the peak is tracked
+    /// for the whole process.
+    pub peak_rss_kb: Option<u64>,
+    pub proof_size: usize,
+}
+
+/// Measures the resources used by a proof. Must be started and finished on
+/// the thread doing the proving, since CPU time is read per thread.
+pub struct ProofMeter {
+    started: Instant,
+    cpu_started: Option<Duration>,
+    track_peak_rss: bool,
+}
+
+impl ProofMeter {
+    /// Starts measuring a proof. `track_peak_rss` resets the process's peak
+    /// RSS, so it must only be set when no other proof is running.
+    pub fn start(track_peak_rss: bool) -> Self {
+        if track_peak_rss {
+            reset_peak_rss();
+        }
+        Self {
+            started: Instant::now(),
+            cpu_started: thread_cpu_time(),
+            track_peak_rss,
+        }
+    }
+
+    pub fn finish(self, proof_size: usize) -> ProofMetrics {
+        let cpu_time = match (self.cpu_started, thread_cpu_time()) {
+            (Some(started), Some(finished)) => Some(finished.saturating_sub(started)),
+            _ => None,
+        };
+        ProofMetrics {
+            wall_time: self.started.elapsed(),
+            cpu_time,
+            peak_rss_kb: self.track_peak_rss.then(peak_rss_kb).flatten(),
+            proof_size,
+        }
+    }
+}
+
+/// Builds the telemetry sent with a proof submission.
+pub fn node_telemetry(
+    capabilities: Option<&NodeCapabilities>,
+    location: Option<String>,
+    metrics: Option<&ProofMetrics>,
+) -> NodeTelemetry {
+    let (program_memory, total_memory) = get_memory_info();
+    let cpu = CpuInfo::get();
+    NodeTelemetry {
+        flops_per_sec: capabilities.map(NodeCapabilities::flops_per_sec_reported),
+        memory_used: Some(program_memory),
+        memory_capacity: Some(total_memory),
+        location,
+        cpu_model: cpu.model.clone(),
+        cpu_cores: Some(cpu.cores as i32),
+        load_average: load_average(),
+        proof_wall_time_ms: metrics.map(|m| m.wall_time.as_millis() as i64),
+        proof_cpu_time_ms: metrics
+            .and_then(|m| m.cpu_time)
+            .map(|t| t.as_millis() as i64),
+        peak_rss_kb: metrics.and_then(|m| m.peak_rss_kb).map(|kb| kb as i64),
+        proof_size_bytes: metrics.map(|m| m.proof_size as i64),
+        client_version: Some(CLIENT_VERSION.to_string()),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn cpu_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        // x86 reports "model name", ARM reports "Processor" or nothing useful
+        .find(|line| line.starts_with("model name") || line.starts_with("Processor"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+}
+
+/// One-minute load average
+#[cfg(target_os = "linux")]
+fn load_average() -> Option<f64> {
+    let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+    loadavg.split_whitespace().next()?.parse().ok()
+}
+
+/// Time the current thread has spent running on a CPU
+#[cfg(target_os = "linux")]
+fn thread_cpu_time() -> Option<Duration> {
+    // The first field of schedstat is the on-CPU time in nanoseconds
+    let schedstat = std::fs::read_to_string("/proc/thread-self/schedstat").ok()?;
+    let nanos = schedstat.split_whitespace().next()?.parse().ok()?;
+    Some(Duration::from_nanos(nanos))
+}
+
+#[cfg(target_os = "linux")]
+fn peak_rss_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+/// Resets the process's `VmHWM` to its current RSS. Without this the peak
+/// would be the largest proof since startup rather than the current one.
+#[cfg(target_os = "linux")]
+fn reset_peak_rss() {
+    // Failure only leaves the lifetime peak in place
+    let _ = std::fs::write("/proc/self/clear_refs", "5");
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cpu_model() -> Option<String> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn load_average() -> Option<f64> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reset_peak_rss() {}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_rss_kb() -> Option<u64> {
+    None
+}